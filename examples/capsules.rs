@@ -45,14 +45,14 @@ fn main() -> std::io::Result<()> {
     .padding_all(2)
     .background(Palette::grey());
 
-    let mut canvas = Canvas::create(100, 100);
+    let mut canvas = Canvas::create(100, 100).with_anti_aliasing(true);
     let sized = SizeCalculator::resolve_size(&layout, &canvas.bounds(), &mut ());
     let draw_commands = SizeResolver::resolve_draw_commands(&sized, &canvas.bounds());
 
     canvas.execute_draw_commands(&draw_commands);
 
     let mut file = File::create(".debug-output/capsules.ppm")?;
-    file.write(canvas.output().as_bytes())?;
+    file.write_all(canvas.output().as_bytes())?;
 
     Ok(())
 }
@@ -22,7 +22,7 @@ fn main() -> std::io::Result<()> {
         let shape = Node::Shape(
             Shape::Rectangle,
             stroke
-                .map(|x| ShapeBehavior::Stroke(x))
+                .map(ShapeBehavior::Stroke)
                 .unwrap_or(ShapeBehavior::Fill),
             color,
         );
@@ -58,7 +58,7 @@ fn main() -> std::io::Result<()> {
             Node::text("Normal Stack", RGBPixel::white()).padding_top(1),
             Node::grid::<(), _, _>(
                 &Matrix::with_rows(
-                    &vec![
+                    &[
                         Alignment::top_left(),
                         Alignment::top(),
                         Alignment::top_right(),
@@ -86,7 +86,7 @@ fn main() -> std::io::Result<()> {
 
     let mut output = File::create(".debug-output/depth_stack.ppm")?;
 
-    output.write(canvas.output().as_bytes())?;
+    output.write_all(canvas.output().as_bytes())?;
 
     Ok(())
 }
@@ -96,6 +96,8 @@ fn alignment_to_text(alignment: &Alignment) -> String {
         VerticalAlignment::Top => 'T',
         VerticalAlignment::Center => 'C',
         VerticalAlignment::Bottom => 'B',
+        VerticalAlignment::FirstBaseline => 'F',
+        VerticalAlignment::LastBaseline => 'L',
     };
 
     let horizontal = match alignment.horizontal() {
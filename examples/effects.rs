@@ -0,0 +1,45 @@
+// A card with a drop shadow next to a blurred one.
+use std::{
+    fs::File,
+    io::{Result, Write},
+};
+
+use clothes::{
+    fonts::Font,
+    graphics::{canvas::Canvas, pixel::RGBPixel},
+    layout::{
+        geometry::{Shape, Vector},
+        node::{Node, ShapeBehavior},
+        size_resolution::{SizeCalculator, SizeResolver},
+    },
+    output::ppm_output::PpmOutput,
+    rendering::canvas::Drawable,
+};
+
+fn card(title: &str) -> Node<RGBPixel, ()> {
+    Node::text_with_font(title, RGBPixel::black(), Font::five_by_seven())
+        .padding_all(6)
+        .as_background(|| Node::Shape(Shape::rounded_rect(3), ShapeBehavior::Fill, RGBPixel::white()))
+}
+
+fn main() -> Result<()> {
+    let shadow = RGBPixel::new(0x30, 0x30, 0x50);
+
+    let layout = Node::horizontal_stack(vec![
+        card("Sharp").shadow(2, Vector::new(2, 3), shadow),
+        card("Blurry").blur(1).padding_left(8),
+    ])
+    .padding_all(8)
+    .background(RGBPixel::blue());
+
+    let mut canvas = Canvas::create(110, 40).with_anti_aliasing(true);
+    let sized = SizeCalculator::resolve_size(&layout, &canvas.bounds(), &mut ());
+    let draw_commands = SizeResolver::resolve_draw_commands(&sized, &canvas.bounds());
+
+    canvas.execute_draw_commands(&draw_commands);
+
+    let mut file = File::create(".debug-output/effects.ppm")?;
+    file.write_all(canvas.output().as_bytes())?;
+
+    Ok(())
+}
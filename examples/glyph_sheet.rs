@@ -0,0 +1,58 @@
+// Draws every glyph of the built-in fonts, and lists the problems found in their glyph tables.
+use std::{fs::File, io::Write, sync::Arc};
+
+use clothes::{
+    fonts::Font,
+    graphics::{canvas::Canvas, pixel::RGBPixel},
+    layout::{
+        geometry::Rect,
+        node::Node,
+        size_resolution::{SizeCalculator, SizeResolver},
+    },
+    output::ppm_output::PpmOutput,
+    rendering::canvas::Drawable,
+};
+
+fn main() -> std::io::Result<()> {
+    let fonts = [
+        Font::three_by_three(),
+        Font::four_by_four(),
+        Font::four_by_five(),
+        Font::five_by_seven(),
+        Font::six_by_eight(),
+    ];
+
+    for font in fonts {
+        println!("{}:\n{}\n", font.name, font.report());
+
+        draw_sheet(font)?;
+    }
+
+    Ok(())
+}
+
+fn draw_sheet(font: Arc<Font>) -> std::io::Result<()> {
+    let name = font.name.clone();
+
+    let layout: Node<RGBPixel, ()> = Node::glyph_sheet(font, Font::five_by_seven(), RGBPixel::black(), 16)
+        .padding_all(2)
+        .background(RGBPixel::white());
+
+    // Measured without limits first, to make the canvas as big as the sheet.
+    let sized = SizeCalculator::resolve_size(&layout, &Rect::sized(1000, 1000), &mut ());
+    let width = sized.sizing.horizontal.min_content_size();
+    let height = sized.sizing.vertical.min_content_size();
+
+    let mut canvas = Canvas::create(width, height);
+
+    let sized = SizeCalculator::resolve_size(&layout, &canvas.bounds(), &mut ());
+    let draw_commands = SizeResolver::resolve_draw_commands(&sized, &canvas.bounds());
+
+    canvas.execute_draw_commands(&draw_commands);
+
+    let mut output = File::create(format!(".debug-output/glyph_sheet_{name}.ppm"))?;
+
+    output.write_all(canvas.output().as_bytes())?;
+
+    Ok(())
+}
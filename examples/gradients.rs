@@ -0,0 +1,53 @@
+// Gradient bars, like the ones of a dashboard, over a radial gradient background.
+use std::{
+    fs::File,
+    io::{Result, Write},
+};
+
+use clothes::{
+    graphics::{canvas::Canvas, gradient::Gradient, pixel::RGBPixel},
+    layout::{
+        geometry::Shape,
+        node::Node,
+        size_resolution::{SizeCalculator, SizeResolver},
+    },
+    output::ppm_output::PpmOutput,
+    rendering::canvas::Drawable,
+};
+
+fn bar(label: &str, width: usize) -> Node<RGBPixel, ()> {
+    let fill = Gradient::horizontal()
+        .stop(0.0, RGBPixel::green())
+        .stop(0.6, RGBPixel::new(0xf0, 0xc0, 0x19))
+        .stop(1.0, RGBPixel::red());
+
+    Node::vertical_stack(vec![
+        Node::text(label, RGBPixel::white()).align_left(),
+        Node::GradientShape(Shape::rounded_rect(2), fill).width(width).height(6).align_left(),
+    ])
+}
+
+fn main() -> Result<()> {
+    let background = Gradient::radial((0.5, 0.0), 1.0)
+        .stop(0.0, RGBPixel::blue())
+        .stop(1.0, RGBPixel::black());
+
+    let layout = Node::VerticalStack(
+        clothes::layout::alignment::HorizontalAlignment::Left,
+        3,
+        vec![bar("cpu", 80), bar("mem", 50), bar("net", 20)],
+    )
+    .padding_all(4)
+    .background_gradient(background);
+
+    let mut canvas = Canvas::create(96, 48).with_anti_aliasing(true);
+    let sized = SizeCalculator::resolve_size(&layout, &canvas.bounds(), &mut ());
+    let draw_commands = SizeResolver::resolve_draw_commands(&sized, &canvas.bounds());
+
+    canvas.execute_draw_commands(&draw_commands);
+
+    let mut file = File::create(".debug-output/gradients.ppm")?;
+    file.write_all(canvas.output().as_bytes())?;
+
+    Ok(())
+}
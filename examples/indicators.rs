@@ -0,0 +1,68 @@
+// A battery gauge, a storage pie chart and a few badges, with dividers between them.
+use std::{
+    fs::File,
+    io::{Result, Write},
+};
+
+use clothes::{
+    graphics::{canvas::Canvas, pixel::RGBPixel},
+    layout::{
+        alignment::{Alignment, VerticalAlignment},
+        geometry::Shape,
+        node::{Node, ShapeBehavior},
+        size_resolution::{SizeCalculator, SizeResolver},
+    },
+    output::ppm_output::PpmOutput,
+    rendering::canvas::Drawable,
+};
+
+/// A gauge going clockwise from the bottom left to the bottom right, filled up to `level`.
+fn gauge(level: f32) -> Node<RGBPixel, ()> {
+    let (start, sweep) = (135.0, 270.0);
+
+    Node::NormalStack(Alignment::center(), vec![
+        Node::Shape(Shape::Arc(start, start + sweep), ShapeBehavior::Stroke(3), RGBPixel::black()),
+        Node::Shape(Shape::Arc(start, start + sweep * level), ShapeBehavior::Stroke(3), RGBPixel::green()),
+        Node::text(&format!("{}%", (level * 100.0) as usize), RGBPixel::black()),
+    ])
+    .width(32)
+    .height(32)
+}
+
+/// A pie chart of how much is `used`, starting from the top.
+fn pie(used: f32) -> Node<RGBPixel, ()> {
+    Node::NormalStack(Alignment::center(), vec![
+        Node::Shape(Shape::Ellipse, ShapeBehavior::Fill, RGBPixel::black()),
+        Node::Shape(Shape::Pie(-90.0, -90.0 + 360.0 * used), ShapeBehavior::Fill, RGBPixel::red()),
+    ])
+    .width(28)
+    .height(28)
+}
+
+fn main() -> Result<()> {
+    let badges = Node::vertical_stack(vec![
+        Node::Shape(Shape::Star(5, 0.45), ShapeBehavior::Fill, RGBPixel::blue()).width(14).height(14),
+        Node::Shape(Shape::Polygon(6), ShapeBehavior::Stroke(1), RGBPixel::black()).width(14).height(14),
+    ]);
+
+    let layout = Node::HorizontalStack(VerticalAlignment::Center, 3, vec![
+        gauge(0.7),
+        Node::divider(RGBPixel::black()),
+        pie(0.35),
+        Node::divider(RGBPixel::black()),
+        badges,
+    ])
+    .padding_all(4)
+    .background(RGBPixel::white());
+
+    let mut canvas = Canvas::create(96, 40).with_anti_aliasing(true);
+    let sized = SizeCalculator::resolve_size(&layout, &canvas.bounds(), &mut ());
+    let draw_commands = SizeResolver::resolve_draw_commands(&sized, &canvas.bounds());
+
+    canvas.execute_draw_commands(&draw_commands);
+
+    let mut file = File::create(".debug-output/indicators.ppm")?;
+    file.write_all(canvas.output().as_bytes())?;
+
+    Ok(())
+}
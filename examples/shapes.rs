@@ -6,7 +6,7 @@ use std::{
 use clothes::{
     graphics::{canvas::Canvas, pixel::RGBPixel},
     layout::{
-        alignment::Alignment, geometry::{CornerRadius, Shape}, node::{Node, ShapeBehavior}, size_resolution::{SizeCalculator, SizeResolver}
+        alignment::Alignment, geometry::{CornerRadius, Shape}, node::{Node, ShapeBehavior}, path::Path, size_resolution::{SizeCalculator, SizeResolver}
     },
     output::ppm_output::PpmOutput,
     rendering::canvas::Drawable,
@@ -27,6 +27,11 @@ fn main() -> Result<()> {
             Node::vertical_stack(vec![
                 Node::Shape(Shape::Ellipse, ShapeBehavior::Stroke(1), RGBPixel::green()),
                 Node::Shape(Shape::Ellipse, ShapeBehavior::Fill, RGBPixel::black()),
+                Node::Shape(
+                    Shape::Path(Path::unit().move_to(0.5, 0.0).line_to(1.0, 1.0).line_to(0.0, 1.0).close()),
+                    ShapeBehavior::Fill,
+                    RGBPixel::green(),
+                ),
             ]).padding_horizontal(6).padding_vertical(3),
         ])
         .width(60)
@@ -42,7 +47,7 @@ fn main() -> Result<()> {
     canvas.execute_draw_commands(&draw_commands);
 
     let mut file = File::create(".debug-output/shapes.ppm")?;
-    file.write(canvas.output().as_bytes())?;
+    file.write_all(canvas.output().as_bytes())?;
 
     Ok(())
 }
@@ -1,6 +1,6 @@
 use std::{fs::File, io::Write};
 
-use clothes::{dictionary, graphics::{canvas::Canvas, pixel::RGBPixel}, layout::{alignment::{Edge, HorizontalAlignment, VerticalAlignment}, node::Node, size_resolution::{SizeCalculator, SizeResolver}}, output::ppm_output::PpmOutput, rendering::canvas::Drawable};
+use clothes::{graphics::{canvas::Canvas, pixel::RGBPixel}, layout::{alignment::{Edge, VerticalAlignment}, node::Node, size_resolution::{SizeCalculator, SizeResolver}}, output::ppm_output::PpmOutput, rendering::canvas::Drawable};
 
 extern crate clothes;
 
@@ -26,7 +26,7 @@ fn main() -> std::io::Result<()> {
 
     let mut output = File::create(".debug-output/simple.ppm")?;
 
-    output.write(canvas.output().as_bytes())?;
+    output.write_all(canvas.output().as_bytes())?;
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,204 @@
+use std::{collections::HashMap, error::Error, fmt::Display};
+
+use crate::layout::geometry::Size;
+
+use super::{Font, Glyph, GlyphPoint};
+
+/// Why a glyph couldn't be read from its ASCII art.
+#[derive(Debug, PartialEq)]
+pub enum ArtError {
+    /// The art has a different number of rows than the glyph size.
+    WrongHeight(usize),
+    /// A row (counting from 0) has a different number of points than the glyph size.
+    WrongWidth { row: usize, width: usize },
+    /// A character that isn't a point.
+    UnknownPoint(char)
+}
+
+impl Error for ArtError {}
+
+impl Display for ArtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FontBuilderError {
+    /// The art of the glyph for the character is wrong.
+    Glyph(char, ArtError),
+    /// The art of the unknown glyph is wrong.
+    UnknownGlyph(ArtError)
+}
+
+impl Error for FontBuilderError {}
+
+impl Display for FontBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self))
+    }
+}
+
+impl Glyph {
+    /// A glyph drawn as rows of text, one per line. `#` is a point drawn with the content of the text,
+    /// `.` an empty point, and a digit a point drawn with that entry of the text's palette.
+    /// Blank lines and the whitespace around rows are skipped, so the art can be indented with the code.
+    pub fn from_art(art: &str, size: &Size) -> Result<Glyph, ArtError> {
+        let rows: Vec<&str> = art.lines().map(str::trim).filter(|row| !row.is_empty()).collect();
+
+        if rows.len() != size.height {
+            return Err(ArtError::WrongHeight(rows.len()));
+        }
+
+        let mut points = Vec::with_capacity(size.width * size.height);
+
+        for (index, row) in rows.into_iter().enumerate() {
+            let width = row.chars().count();
+
+            if width != size.width {
+                return Err(ArtError::WrongWidth { row: index, width });
+            }
+
+            for c in row.chars() {
+                let point = match c {
+                    '#' => GlyphPoint::Ink,
+                    '.' => GlyphPoint::Empty,
+                    digit @ '0'..='9' => GlyphPoint::Palette(digit as u8 - b'0'),
+                    other => return Err(ArtError::UnknownPoint(other))
+                };
+
+                points.push(point);
+            }
+        }
+
+        Ok(Glyph::new(points))
+    }
+}
+
+/// Builds a `Font` out of glyphs written as ASCII art, see `Glyph::from_art`.
+/// Problems in the art are kept until `build`, which returns the first one.
+#[derive(Clone, Debug)]
+pub struct FontBuilder {
+    name: String,
+    size: Size,
+    glyphs: Vec<(String, String)>,
+    unknown_glyph: Option<String>,
+    character_spacing: usize,
+    line_spacing: usize,
+    space_width: Option<usize>,
+    descent: usize
+}
+
+impl FontBuilder {
+    /// A font whose glyphs are all `size` points big. By default glyphs are a point apart,
+    /// lines are a point apart, and spaces are as wide as a glyph with spacing on both sides.
+    pub fn new(name: &str, size: Size) -> Self {
+        FontBuilder {
+            name: name.to_string(),
+            size,
+            glyphs: vec![],
+            unknown_glyph: None,
+            character_spacing: 1,
+            line_spacing: 1,
+            space_width: None,
+            descent: 0
+        }
+    }
+
+    /// Draws every character of `chars` with the glyph in `art`, replacing glyphs added before for them.
+    pub fn glyph(mut self, chars: &str, art: &str) -> Self {
+        self.glyphs.push((chars.to_string(), art.to_string()));
+
+        self
+    }
+
+    /// The glyph drawn for characters the font doesn't have. Left blank by default.
+    pub fn unknown_glyph(mut self, art: &str) -> Self {
+        self.unknown_glyph = Some(art.to_string());
+
+        self
+    }
+
+    pub fn character_spacing(mut self, spacing: usize) -> Self {
+        self.character_spacing = spacing;
+
+        self
+    }
+
+    pub fn line_spacing(mut self, spacing: usize) -> Self {
+        self.line_spacing = spacing;
+
+        self
+    }
+
+    pub fn space_width(mut self, width: usize) -> Self {
+        self.space_width = Some(width);
+
+        self
+    }
+
+    /// Rows at the bottom of the glyphs that go below the baseline.
+    pub fn descent(mut self, descent: usize) -> Self {
+        self.descent = descent;
+
+        self
+    }
+
+    pub fn build(self) -> Result<Font, FontBuilderError> {
+        let mut glyphs = HashMap::new();
+
+        for (chars, art) in &self.glyphs {
+            for c in chars.chars() {
+                let glyph = Glyph::from_art(art, &self.size).map_err(|error| FontBuilderError::Glyph(c, error))?;
+
+                glyphs.insert(c, glyph);
+            }
+        }
+
+        let space_width = self.space_width.unwrap_or(self.character_spacing * 2 + self.size.width);
+
+        let mut font = Font::new(&self.name, self.size.clone(), glyphs)
+            .with_character_spacing(self.character_spacing)
+            .with_line_spacing(self.line_spacing)
+            .with_space_width(space_width)
+            .with_descent(self.descent);
+
+        if let Some(art) = &self.unknown_glyph {
+            font.unknown_glyph = Glyph::from_art(art, &self.size).map_err(FontBuilderError::UnknownGlyph)?;
+        }
+
+        Ok(font)
+    }
+}
+
+#[test]
+fn builds_font_from_art() {
+    use crate::layout::geometry::Rect;
+
+    let font = FontBuilder::new("Brand", Size::new(3, 3))
+        .glyph("hH", "
+            #.#
+            ###
+            #.#")
+        .glyph("i", ".#.\n.#.\n.1.")
+        .character_spacing(2)
+        .build()
+        .unwrap();
+
+    let (x, o) = (GlyphPoint::Ink, GlyphPoint::Empty);
+
+    assert_eq!(font.get_glyph(&'H'), &Glyph::new(vec![x, o, x, x, x, x, x, o, x]));
+    assert_eq!(font.get_glyph(&'h'), font.get_glyph(&'H'));
+    assert_eq!(font.get_glyph(&'i').points()[7], GlyphPoint::Palette(1));
+    assert_eq!(font.space_width(), 7);
+
+    let text = font.calculate_lines("hi", &Rect::sized(20, 20));
+    let offsets: Vec<_> = text.lines().flat_map(|line| line.glyphs.iter().map(|glyph| glyph.offset().width)).collect();
+    assert_eq!(offsets, vec![0, 5]);
+
+    let wrong_width = FontBuilder::new("Brand", Size::new(3, 3)).glyph("a", "###\n##\n###").build();
+    assert_eq!(wrong_width.unwrap_err(), FontBuilderError::Glyph('a', ArtError::WrongWidth { row: 1, width: 2 }));
+
+    let wrong_point = FontBuilder::new("Brand", Size::new(1, 1)).unknown_glyph("?").build();
+    assert_eq!(wrong_point.unwrap_err(), FontBuilderError::UnknownGlyph(ArtError::UnknownPoint('?')));
+}
@@ -0,0 +1,219 @@
+use std::{collections::HashMap, sync::Arc};
+
+use once_cell::sync::Lazy;
+
+use crate::layout::geometry::Size;
+
+use super::{insert_box_drawing, insert_unaccented_letters, Font, Glyph, GlyphPoint, TAB_SPACES};
+
+/// Columns of the printable ASCII glyphs, from `' '` to `'~'`, left to right.
+/// Bit `n` of a column is the point on row `n`, counting from the top.
+const FIVE_BY_SEVEN_COLUMNS: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // !
+    [0x00, 0x07, 0x00, 0x07, 0x00], // "
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // #
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // $
+    [0x23, 0x13, 0x08, 0x64, 0x62], // %
+    [0x36, 0x49, 0x55, 0x22, 0x50], // &
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // (
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // )
+    [0x14, 0x08, 0x3E, 0x08, 0x14], // *
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // +
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ,
+    [0x08, 0x08, 0x08, 0x08, 0x08], // -
+    [0x00, 0x60, 0x60, 0x00, 0x00], // .
+    [0x20, 0x10, 0x08, 0x04, 0x02], // /
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // 0
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // 1
+    [0x42, 0x61, 0x51, 0x49, 0x46], // 2
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // 3
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // 4
+    [0x27, 0x45, 0x45, 0x45, 0x39], // 5
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // 6
+    [0x01, 0x71, 0x09, 0x05, 0x03], // 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], // 8
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // 9
+    [0x00, 0x36, 0x36, 0x00, 0x00], // :
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ;
+    [0x08, 0x14, 0x22, 0x41, 0x00], // <
+    [0x14, 0x14, 0x14, 0x14, 0x14], // =
+    [0x00, 0x41, 0x22, 0x14, 0x08], // >
+    [0x02, 0x01, 0x51, 0x09, 0x06], // ?
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // @
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // A
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // B
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // C
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // D
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // E
+    [0x7F, 0x09, 0x09, 0x01, 0x01], // F
+    [0x3E, 0x41, 0x41, 0x51, 0x32], // G
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // H
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // I
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // J
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // K
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // L
+    [0x7F, 0x02, 0x04, 0x02, 0x7F], // M
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // N
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // O
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // P
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // Q
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // R
+    [0x46, 0x49, 0x49, 0x49, 0x31], // S
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // T
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // U
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // V
+    [0x7F, 0x20, 0x18, 0x20, 0x7F], // W
+    [0x63, 0x14, 0x08, 0x14, 0x63], // X
+    [0x03, 0x04, 0x78, 0x04, 0x03], // Y
+    [0x61, 0x51, 0x49, 0x45, 0x43], // Z
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // [
+    [0x02, 0x04, 0x08, 0x10, 0x20], // \
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], // ^
+    [0x40, 0x40, 0x40, 0x40, 0x40], // _
+    [0x00, 0x01, 0x02, 0x04, 0x00], // `
+    [0x20, 0x54, 0x54, 0x54, 0x78], // a
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // b
+    [0x38, 0x44, 0x44, 0x44, 0x20], // c
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // d
+    [0x38, 0x54, 0x54, 0x54, 0x18], // e
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // f
+    [0x08, 0x14, 0x54, 0x54, 0x3C], // g
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // h
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // i
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // j
+    [0x00, 0x7F, 0x10, 0x28, 0x44], // k
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // l
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // m
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // n
+    [0x38, 0x44, 0x44, 0x44, 0x38], // o
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // p
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // q
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // r
+    [0x48, 0x54, 0x54, 0x54, 0x20], // s
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // t
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // u
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // v
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // w
+    [0x44, 0x28, 0x10, 0x28, 0x44], // x
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // y
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // z
+    [0x00, 0x08, 0x36, 0x41, 0x00], // {
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // |
+    [0x00, 0x41, 0x36, 0x08, 0x00], // }
+    [0x08, 0x04, 0x08, 0x10, 0x08], // ~
+];
+
+/// Lowercase letters that go below the baseline in the 6x8 font, instead of being squeezed above it.
+const SIX_BY_EIGHT_DESCENDERS: [(char, [u8; 5]); 5] = [
+    ('g', [0x18, 0xA4, 0xA4, 0xA4, 0x7C]),
+    ('j', [0x40, 0x80, 0x80, 0x7A, 0x00]),
+    ('p', [0xFC, 0x24, 0x24, 0x24, 0x18]),
+    ('q', [0x18, 0x24, 0x24, 0x24, 0xFC]),
+    ('y', [0x1C, 0xA0, 0xA0, 0xA0, 0x7C]),
+];
+
+static FIVE_BY_SEVEN_MONO: Lazy<Arc<Font>> = Lazy::new(|| {
+    let size = Size::new(5, 7);
+    let character_spacing = 1;
+    let space_width = character_spacing * 2 + size.width;
+
+    Arc::new(Font {
+        name: "5x7Mono".to_string(),
+        glyphs: Arc::new(classic_glyphs(&size, &[])),
+        unknown_glyph: checkerboard_glyph(&size),
+        size,
+        space_width,
+        line_spacing: 1,
+        character_spacing,
+        descent: 0,
+        tab_stops: vec![],
+        tab_width: space_width * TAB_SPACES,
+        fallbacks: vec![]
+    })
+});
+
+static SIX_BY_EIGHT_MONO: Lazy<Arc<Font>> = Lazy::new(|| {
+    // The sixth column and eighth row are left blank by most glyphs, so they are spaced out on their own.
+    let size = Size::new(6, 8);
+    let space_width = size.width;
+
+    Arc::new(Font {
+        name: "6x8Mono".to_string(),
+        glyphs: Arc::new(classic_glyphs(&size, &SIX_BY_EIGHT_DESCENDERS)),
+        unknown_glyph: checkerboard_glyph(&size),
+        size,
+        space_width,
+        line_spacing: 0,
+        character_spacing: 0,
+        descent: 1,
+        tab_stops: vec![],
+        tab_width: space_width * TAB_SPACES,
+        fallbacks: vec![]
+    })
+});
+
+impl Font {
+    /// A 5x7 font with a glyph for every printable ASCII character.
+    pub fn five_by_seven() -> Arc<Self> {
+        FIVE_BY_SEVEN_MONO.clone()
+    }
+
+    /// A 6x8 font with a glyph for every printable ASCII character, with room for descenders.
+    pub fn six_by_eight() -> Arc<Self> {
+        SIX_BY_EIGHT_MONO.clone()
+    }
+}
+
+fn classic_glyphs(size: &Size, overrides: &[(char, [u8; 5])]) -> HashMap<char, Glyph> {
+    let mut glyphs: HashMap<char, Glyph> = (' '..='~')
+        .zip(FIVE_BY_SEVEN_COLUMNS)
+        .filter(|(c, _)| *c != ' ')
+        .map(|(c, columns)| (c, glyph_from_columns(&columns, size)))
+        .collect();
+
+    for (c, columns) in overrides {
+        glyphs.insert(*c, glyph_from_columns(columns, size));
+    }
+
+    insert_unaccented_letters(&mut glyphs);
+    insert_box_drawing(&mut glyphs, size);
+
+    glyphs
+}
+
+/// A glyph of `size` whose columns are set from the bits of `columns`, the rest left empty.
+fn glyph_from_columns(columns: &[u8], size: &Size) -> Glyph {
+    let mut points = vec![GlyphPoint::Empty; size.width * size.height];
+
+    for (x, column) in columns.iter().take(size.width).enumerate() {
+        for y in 0..size.height.min(8) {
+            points[y * size.width + x] = GlyphPoint::from(column & (1 << y) != 0);
+        }
+    }
+
+    Glyph::new(points)
+}
+
+fn checkerboard_glyph(size: &Size) -> Glyph {
+    let points = (0..size.width * size.height)
+        .map(|index| (index / size.width + index % size.width).is_multiple_of(2))
+        .collect::<Vec<_>>();
+
+    Glyph::from(points)
+}
+
+#[test]
+fn covers_printable_ascii() {
+    for font in [Font::five_by_seven(), Font::six_by_eight()] {
+        let report = font.report();
+
+        assert!(report.is_empty(), "{}:\n{report}", font.name);
+    }
+
+    let six_by_eight = Font::six_by_eight();
+    assert_eq!(six_by_eight.ascent(), 7);
+    assert_eq!(six_by_eight.size('g').height, 8);
+}
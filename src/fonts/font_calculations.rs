@@ -1,20 +1,28 @@
-use std::slice::Iter;
+use std::{ops::Range, slice::Iter};
 
 use crate::layout::geometry::{Rect, Size};
 
-use super::{ascii::Ascii, Font, Glyph};
+use super::{Font, Glyph};
 
 #[derive(Clone, PartialEq)]
 pub struct ResolvedGlyph<'glyph> {
     offset: Size,
     glyph: &'glyph Glyph,
-    size: Size
+    size: Size,
+    /// Index of the span (font) the glyph was taken from.
+    span: usize,
+    /// Space left after the glyph when another glyph follows it.
+    spacing: usize,
+    /// Rows of the glyph above the baseline.
+    ascent: usize,
+    /// Byte index of the glyph's character in the laid out text.
+    index: usize
 }
 
 #[derive(Clone, PartialEq)]
 pub enum ResolvedGlyphElement<'glyph> {
     Glyph(ResolvedGlyph<'glyph>),
-    Space
+    Space(usize)
 }
 
 #[derive(Clone)]
@@ -22,6 +30,8 @@ pub struct ResolvedLine<'source, 'glyph> {
     line_text: &'source str,
     pub glyphs: Vec<ResolvedGlyph<'glyph>>,
     bounds: Rect,
+    /// Distance from the top of the line to the baseline its glyphs sit on.
+    baseline: usize
 }
 
 pub struct ResolvedText<'source, 'glyph> {
@@ -30,11 +40,15 @@ pub struct ResolvedText<'source, 'glyph> {
 }
 
 impl<'glyph> ResolvedGlyph<'glyph> {
-    fn new(offset: Size, glyph: &'glyph Glyph, size: Size) -> Self {
+    fn new(offset: Size, glyph: &'glyph Glyph, size: Size, span: usize, spacing: usize, ascent: usize, index: usize) -> Self {
         ResolvedGlyph {
             offset,
             glyph,
-            size
+            size,
+            span,
+            spacing,
+            ascent,
+            index
         }
     }
 
@@ -49,6 +63,14 @@ impl<'glyph> ResolvedGlyph<'glyph> {
     pub fn offset(&self) -> &Size {
         &self.offset
     }
+
+    pub fn span(&self) -> usize {
+        self.span
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
 }
 
 impl<'source> ResolvedLine<'source, '_> {
@@ -59,16 +81,30 @@ impl<'source> ResolvedLine<'source, '_> {
     pub fn line(&self) -> &'source str {
         self.line_text
     }
+
+    pub fn baseline(&self) -> usize {
+        self.baseline
+    }
 }
 
 impl ResolvedText<'_, '_> {
-    pub fn lines(&self) -> Iter<ResolvedLine> {
+    pub fn lines(&self) -> Iter<'_, ResolvedLine<'_, '_>> {
         self.lines.iter()
     }
 
     pub fn size(&self) -> &Size {
         &self.size
     }
+
+    /// Distance from the top of the text to the baseline of its first line.
+    pub fn first_baseline(&self) -> Option<usize> {
+        self.lines.first().map(|line| line.bounds.y as usize + line.baseline)
+    }
+
+    /// Distance from the top of the text to the baseline of its last line.
+    pub fn last_baseline(&self) -> Option<usize> {
+        self.lines.last().map(|line| line.bounds.y as usize + line.baseline)
+    }
 }
 
 impl Font {
@@ -77,6 +113,21 @@ impl Font {
         text: &'str str,
         bounds: &Rect,
     ) -> ResolvedText<'str, 'font> {
+        Font::calculate_span_lines(text, &[(0..text.len(), self)], bounds)
+    }
+
+    /// Lays out a text whose parts are drawn with different fonts, wrapping them together as one paragraph.
+    /// `spans` are the byte ranges of `text` each font is used for, in order.
+    /// Every resolved glyph keeps the index of the span it belongs to.
+    pub fn calculate_span_lines<'str, 'font>(
+        text: &'str str,
+        spans: &[(Range<usize>, &'font Font)],
+        bounds: &Rect,
+    ) -> ResolvedText<'str, 'font> {
+        let Some((_, first_font)) = spans.first() else {
+            return ResolvedText { lines: vec![], size: Size::zero() };
+        };
+
         let char_indices = text.char_indices();
         
         let mut context = LineResolution {
@@ -84,29 +135,35 @@ impl Font {
             bounds,
             source_string: text,
             current_glyphs: vec![],
-            font: self,
-            character_height: 0,
+            // Lines are as far apart as the fonts that need it the most.
+            line_spacing: spans.iter().map(|(_, font)| font.line_spacing()).max().unwrap_or(0),
+            line_ascent: 0,
+            line_descent: 0,
+            font_metrics: (first_font.ascent(), first_font.descent()),
             previous_starting_index: 0
         };
 
         let mut overflowing_y = false;
+        let mut span = 0;
 
         for (index, c) in char_indices {
+            while span + 1 < spans.len() && index >= spans[span].0.end {
+                span += 1;
+            }
+
+            let font = spans[span].1;
+            context.font_metrics = (font.ascent(), font.descent());
+
             if c == '\n' {
                 context.commit_line(Some(index));
                 continue;
             }
             
-            let default_char = b'|';
-
-            // FIXME: Calculating only first character. Unicode will not work
-            let element_ascii = Ascii::try_from(c)
-                .unwrap_or(Ascii(default_char));
-
-            let element_glyph = self.get_glyph(&element_ascii);
-            let element_size = self.size(element_ascii);
+            // Characters without a glyph in the font (or its fallbacks) are drawn with the unknown glyph.
+            let element_glyph = font.get_glyph(&c);
+            let element_size = font.size(c);
             
-            context.commit_glyph(index, element_ascii, element_glyph, element_size.clone());
+            context.commit_glyph(index, c, element_glyph, element_size.clone(), span, font);
 
             let line_y = context.line_y();
             if line_y + element_size.height > bounds.height {
@@ -126,17 +183,21 @@ impl Font {
     }
 }
 
-struct LineResolution<'source, 'font, 'bounds, 'glyph> {
+struct LineResolution<'source, 'bounds, 'glyph> {
     fitting_lines: Vec<ResolvedLine<'source, 'glyph>>,
     bounds: &'bounds Rect,
     source_string: &'source str,
     current_glyphs: Vec<ResolvedGlyphElement<'glyph>>,
-    font: &'font Font,
-    character_height: usize,
+    line_spacing: usize,
+    /// Rows above and below the baseline needed by the glyphs of the current line.
+    line_ascent: usize,
+    line_descent: usize,
+    /// Ascent and descent of the font in use, given to lines without glyphs.
+    font_metrics: (usize, usize),
     previous_starting_index: usize
 }
 
-impl<'glyph> LineResolution<'_, '_, '_, 'glyph> {
+impl<'glyph> LineResolution<'_, '_, 'glyph> {
     fn commit_line(&mut self, index_end: Option<usize>) {
         let subline;
         
@@ -153,10 +214,19 @@ impl<'glyph> LineResolution<'_, '_, '_, 'glyph> {
         if let Some(subline) = subline {
             let line_x = 0;
 
+            let has_glyphs = self.current_glyphs.iter().any(|element| matches!(element, ResolvedGlyphElement::Glyph(_)));
+            let (ascent, descent) = if has_glyphs { (self.line_ascent, self.line_descent) } else { self.font_metrics };
+
+            // Glyphs from other fonts all sit on the same baseline.
             let glyphs = self.current_glyphs.iter().filter_map(|element| {
                 match element {
-                    ResolvedGlyphElement::Space => None,
-                    ResolvedGlyphElement::Glyph(g) => Some(g.clone())
+                    ResolvedGlyphElement::Space(_) => None,
+                    ResolvedGlyphElement::Glyph(g) => {
+                        let mut glyph = g.clone();
+                        glyph.offset.height = ascent - glyph.ascent;
+
+                        Some(glyph)
+                    }
                 }
             });
 
@@ -165,23 +235,30 @@ impl<'glyph> LineResolution<'_, '_, '_, 'glyph> {
             let resolved_line = ResolvedLine {
                 line_text: subline,
                 glyphs: glyphs.collect(),
-                bounds: Rect::new(line_x, line_y as i64, self.current_line_width(), self.character_height), // FIXME: Line height will not work on non-mono
+                bounds: Rect::new(line_x, line_y as i64, self.current_line_width(), ascent + descent),
+                baseline: ascent
             };
 
             self.fitting_lines.push(resolved_line);
             self.current_glyphs = vec![];
+            self.line_ascent = 0;
+            self.line_descent = 0;
         }
     }
 
-    fn commit_glyph(&mut self, char_index: usize, element: Ascii, element_glyph: &'glyph Glyph, element_size: Size) {
-        if element.is_space() {
-            self.current_glyphs.push(ResolvedGlyphElement::Space);
+    fn commit_glyph(&mut self, char_index: usize, element: char, element_glyph: &'glyph Glyph, element_size: Size, span: usize, font: &Font) {
+        if element == ' ' {
+            self.current_glyphs.push(ResolvedGlyphElement::Space(font.space_width()));
             
             return
         }
 
-        if self.character_height == 0 {
-            self.character_height = element_size.height
+        // Tabs are spaces as wide as the distance to the next tab stop.
+        if element == '\t' {
+            let x = self.current_x();
+            self.current_glyphs.push(ResolvedGlyphElement::Space(font.next_tab_stop(x) - x));
+
+            return
         }
         
         let mut x_offset = self.next_glyph_x();
@@ -192,70 +269,79 @@ impl<'glyph> LineResolution<'_, '_, '_, 'glyph> {
             x_offset = 0;
         }
 
+        // Glyphs coming from a fallback font or another span can be taller than the rest of the line.
+        let ascent = font.glyph_font(element).ascent();
+        self.line_ascent = self.line_ascent.max(ascent);
+        self.line_descent = self.line_descent.max(element_size.height - ascent);
+
         let offset = Size::new(x_offset, 0);
 
         let resolved_glyph = ResolvedGlyph::new(
             offset,
             element_glyph,
-            element_size
+            element_size,
+            span,
+            font.character_spacing(),
+            ascent,
+            char_index
         );
 
         self.current_glyphs.push(ResolvedGlyphElement::Glyph(resolved_glyph));
     }
 
     pub fn next_glyph_x(&self) -> usize {
+        let trailing_spacing = match self.current_glyphs.last() {
+            Some(ResolvedGlyphElement::Glyph(glyph)) => glyph.spacing,
+            _ => 0
+        };
+
+        self.current_x() + trailing_spacing
+    }
+
+    /// Where the last element of the line ends.
+    fn current_x(&self) -> usize {
         let mut result = 0;
 
-        let mut prev_was_glyph = false;
+        let mut previous_spacing = None;
 
         for element in self.current_glyphs.iter() {
             match element {
-                ResolvedGlyphElement::Space => {
-                    result += self.font.space_width();
+                ResolvedGlyphElement::Space(width) => {
+                    result += width;
 
-                    prev_was_glyph = false;
+                    previous_spacing = None;
                 },
                 ResolvedGlyphElement::Glyph(glyph) => {
-                    if prev_was_glyph {
-                        result += self.font.character_spacing();
-                    }
-                    
+                    result += previous_spacing.unwrap_or(0);
                     result += glyph.size().width;
 
-                    prev_was_glyph = true;
+                    previous_spacing = Some(glyph.spacing);
                 }
             }
         }
 
-        if prev_was_glyph {
-            result += self.font.character_spacing();
-        }
-
         result
     }
 
     pub fn current_line_width(&self) -> usize {
         let mut total: usize = 0;
 
-        let mut prev_was_glyph = false;
+        let mut previous_spacing = None;
 
         let mut last_before_spaces = 0;
 
         for element in self.current_glyphs.iter() {
             match element {
-                ResolvedGlyphElement::Space => {
-                    total += self.font.space_width();
+                ResolvedGlyphElement::Space(width) => {
+                    total += width;
 
-                    prev_was_glyph = false;
+                    previous_spacing = None;
                 },
                 ResolvedGlyphElement::Glyph(glyph) => {
-                    if prev_was_glyph {
-                        total += self.font.character_spacing();
-                    }
-                    
+                    total += previous_spacing.unwrap_or(0);
                     total += glyph.size().width;
 
-                    prev_was_glyph = true;
+                    previous_spacing = Some(glyph.spacing);
                     last_before_spaces = total;
                 }
             }
@@ -271,7 +357,7 @@ impl<'glyph> LineResolution<'_, '_, '_, 'glyph> {
             .unwrap_or(0);
 
         if max_y != 0 {
-            max_y + self.font.line_spacing()
+            max_y + self.line_spacing
         } else {
             max_y
         }
@@ -289,8 +375,42 @@ impl<'glyph> LineResolution<'_, '_, '_, 'glyph> {
         let all_lines_height = self.fitting_lines.iter().fold(0, |n, line|{
             n + line.bounds.height
         });
-        let line_spacing_sum = self.font.line_spacing() * self.fitting_lines.len().saturating_sub(1);
+        let line_spacing_sum = self.line_spacing * self.fitting_lines.len().saturating_sub(1);
 
         all_lines_height + line_spacing_sum
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn resolves_non_ascii_glyphs() {
+    let font = Font::four_by_five();
+    let text = font.calculate_lines("Straße ñandú → ┼", &Rect::sized(200, 20));
+
+    let lines: Vec<_> = text.lines().collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].glyphs.len(), 13);
+
+    for resolved_glyph in &lines[0].glyphs {
+        assert_ne!(resolved_glyph.glyph(), font.default_glyph());
+    }
+}
+
+#[test]
+fn advances_tabs_to_stops() {
+    let glyph_x = |font: &Font, text: &str| -> Vec<usize> {
+        let resolved = font.calculate_lines(text, &Rect::sized(200, 20));
+
+        resolved.lines()
+            .flat_map(|line| line.glyphs.iter().map(|glyph| glyph.offset().width))
+            .collect()
+    };
+
+    let font = Font::three_by_three();
+    assert_eq!(glyph_x(&font, "a\tb"), vec![0, 20]);
+
+    let font = font.as_ref().clone().with_tab_stops(vec![8]);
+    assert_eq!(glyph_x(&font, "a\tb\tc"), vec![0, 8, 28]);
+
+    let font = font.with_character_spacing(3).with_space_width(2);
+    assert_eq!(glyph_x(&font, "ab c"), vec![0, 6, 11]);
+}
@@ -0,0 +1,374 @@
+use std::{collections::HashMap, sync::Arc};
+
+use once_cell::sync::Lazy;
+
+use crate::layout::geometry::Size;
+
+use super::{Font, Glyph, GlyphPoint};
+
+/// Common UI icons, drawn by the icon fonts.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Icon {
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Check,
+    Cross,
+    Warning,
+    Play,
+    Pause,
+    /// Charge level, from 0 (empty) to `Icon::MAX_BATTERY` (full).
+    Battery(u8),
+    /// Signal strength, from 0 (only the dot) to `Icon::MAX_WIFI` (every arc).
+    Wifi(u8)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum IconSize {
+    /// 5x5, to go along the 4x5 font.
+    #[default]
+    Small,
+    /// 7x7
+    Medium,
+    /// 14x14, the medium icons scaled up.
+    Large
+}
+
+/// Icons are drawn for characters of the private use area, starting here.
+const ICONS_START: u32 = 0xE000;
+const BATTERY_START: u32 = ICONS_START + 0x10;
+const WIFI_START: u32 = ICONS_START + 0x20;
+
+const FIXED_ICONS: [Icon; 9] = [
+    Icon::ArrowUp,
+    Icon::ArrowDown,
+    Icon::ArrowLeft,
+    Icon::ArrowRight,
+    Icon::Check,
+    Icon::Cross,
+    Icon::Warning,
+    Icon::Play,
+    Icon::Pause
+];
+
+static SMALL_ICONS: Lazy<Arc<Font>> = Lazy::new(|| Arc::new(icon_font("Icons5x5", 5, small_icon_art)));
+static MEDIUM_ICONS: Lazy<Arc<Font>> = Lazy::new(|| Arc::new(icon_font("Icons7x7", 7, medium_icon_art)));
+static LARGE_ICONS: Lazy<Arc<Font>> = Lazy::new(|| Arc::new(MEDIUM_ICONS.scaled(2)));
+
+impl Icon {
+    pub const MAX_BATTERY: u8 = 4;
+    pub const MAX_WIFI: u8 = 3;
+
+    /// The character the icon fonts draw this icon for. It can be mixed in with regular text,
+    /// as long as the font of the text has an icon font as a fallback.
+    pub fn char(self) -> char {
+        let code = match self {
+            Icon::Battery(level) => BATTERY_START + level.min(Self::MAX_BATTERY) as u32,
+            Icon::Wifi(strength) => WIFI_START + strength.min(Self::MAX_WIFI) as u32,
+            icon => {
+                let index = FIXED_ICONS.iter().position(|fixed| *fixed == icon).unwrap_or_default();
+
+                ICONS_START + index as u32
+            }
+        };
+
+        char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+
+    fn all() -> Vec<Icon> {
+        let batteries = (0..=Self::MAX_BATTERY).map(Icon::Battery);
+        let wifis = (0..=Self::MAX_WIFI).map(Icon::Wifi);
+
+        FIXED_ICONS.into_iter().chain(batteries).chain(wifis).collect()
+    }
+}
+
+impl Font {
+    /// The font with every `Icon`, at the given size.
+    pub fn icons(size: IconSize) -> Arc<Font> {
+        match size {
+            IconSize::Small => SMALL_ICONS.clone(),
+            IconSize::Medium => MEDIUM_ICONS.clone(),
+            IconSize::Large => LARGE_ICONS.clone()
+        }
+    }
+}
+
+fn icon_font(name: &str, side: usize, art: fn(Icon) -> Option<&'static str>) -> Font {
+    let glyphs: HashMap<char, Glyph> = Icon::all()
+        .into_iter()
+        .map(|icon| {
+            let glyph = match icon {
+                Icon::Battery(level) => battery_glyph(side, level),
+                Icon::Wifi(strength) => wifi_glyph(side, strength),
+                icon => glyph_from_art(art(icon).unwrap_or_default(), side)
+            };
+
+            (icon.char(), glyph)
+        })
+        .collect();
+
+    Font::new(name, Size::new(side, side), glyphs)
+}
+
+/// The glyph of an icon drawn as ASCII art, blank if the art doesn't fit the icon size.
+fn glyph_from_art(art: &str, side: usize) -> Glyph {
+    let size = Size::new(side, side);
+
+    Glyph::from_art(art, &size).unwrap_or_else(|_| Glyph::new(vec![GlyphPoint::Empty; side * side]))
+}
+
+/// A battery pointing right, with its inside filled proportionally to `level`.
+/// The inside fills up column by column, each from the bottom up, so every level looks
+/// different even when there are fewer columns than levels.
+fn battery_glyph(side: usize, level: u8) -> Glyph {
+    // The small battery takes every row, to have enough of the inside for each level.
+    let margin = if side > 5 { 1 } else { 0 };
+    let (top, bottom) = (margin, side - 1 - margin);
+    let body_right = side - 2;
+
+    let inside_height = bottom - top - 1;
+    let inside_points = (body_right - 1) * inside_height;
+    let filled_points = (level.min(Icon::MAX_BATTERY) as usize * inside_points).div_ceil(Icon::MAX_BATTERY as usize);
+
+    // How many points of the inside fill up before the one at `x`, `y`.
+    let fill_order = |x: usize, y: usize| (x - 1) * inside_height + (bottom - 1 - y);
+
+    let mut points = vec![GlyphPoint::Empty; side * side];
+
+    for y in top..=bottom {
+        for x in 0..side {
+            let is_outline = x == 0 || x == body_right || y == top || y == bottom;
+            let is_inside = x > 0 && x < body_right && y > top && y < bottom;
+            let is_terminal = x == side - 1 && y > top && y < bottom;
+
+            let filled = (is_outline && x <= body_right) || is_terminal || (is_inside && fill_order(x, y) < filled_points);
+
+            points[y * side + x] = GlyphPoint::from(filled);
+        }
+    }
+
+    Glyph::new(points)
+}
+
+/// A dot on the bottom left corner with arcs around it, every other point away.
+/// The strength is spread over the arcs that fit.
+fn wifi_glyph(side: usize, strength: u8) -> Glyph {
+    let arc_count = (side - 1) / 2;
+    let shown_arcs = (strength.min(Icon::MAX_WIFI) as usize * arc_count).div_ceil(Icon::MAX_WIFI as usize);
+
+    let mut points = vec![GlyphPoint::Empty; side * side];
+
+    for y in 0..side {
+        for x in 0..side {
+            let dy = side - 1 - y;
+            // Four times the squared distance, to compare it with half-point bands without floats.
+            let distance = 4 * (x * x + dy * dy);
+
+            let on_arc = (1..=shown_arcs).any(|arc| {
+                let radius = 2 * arc;
+
+                distance >= (2 * radius - 1).pow(2) && distance < (2 * radius + 1).pow(2)
+            });
+
+            points[y * side + x] = GlyphPoint::from(distance == 0 || on_arc);
+        }
+    }
+
+    Glyph::new(points)
+}
+
+fn small_icon_art(icon: Icon) -> Option<&'static str> {
+    let art = match icon {
+        Icon::ArrowUp => "
+            ..#..
+            .###.
+            #.#.#
+            ..#..
+            ..#..",
+        Icon::ArrowDown => "
+            ..#..
+            ..#..
+            #.#.#
+            .###.
+            ..#..",
+        Icon::ArrowLeft => "
+            ..#..
+            .#...
+            #####
+            .#...
+            ..#..",
+        Icon::ArrowRight => "
+            ..#..
+            ...#.
+            #####
+            ...#.
+            ..#..",
+        Icon::Check => "
+            .....
+            ....#
+            ...#.
+            #.#..
+            .#...",
+        Icon::Cross => "
+            #...#
+            .#.#.
+            ..#..
+            .#.#.
+            #...#",
+        Icon::Warning => "
+            ..#..
+            .#.#.
+            ##.##
+            #####
+            ##.##",
+        Icon::Play => "
+            #....
+            ###..
+            #####
+            ###..
+            #....",
+        Icon::Pause => "
+            ##.##
+            ##.##
+            ##.##
+            ##.##
+            ##.##",
+        Icon::Battery(_) | Icon::Wifi(_) => return None
+    };
+
+    Some(art)
+}
+
+fn medium_icon_art(icon: Icon) -> Option<&'static str> {
+    let art = match icon {
+        Icon::ArrowUp => "
+            ...#...
+            ..###..
+            .#.#.#.
+            #..#..#
+            ...#...
+            ...#...
+            ...#...",
+        Icon::ArrowDown => "
+            ...#...
+            ...#...
+            ...#...
+            #..#..#
+            .#.#.#.
+            ..###..
+            ...#...",
+        Icon::ArrowLeft => "
+            ...#...
+            ..#....
+            .#.....
+            #######
+            .#.....
+            ..#....
+            ...#...",
+        Icon::ArrowRight => "
+            ...#...
+            ....#..
+            .....#.
+            #######
+            .....#.
+            ....#..
+            ...#...",
+        Icon::Check => "
+            .......
+            ......#
+            .....#.
+            #...#..
+            .#.#...
+            ..#....
+            .......",
+        Icon::Cross => "
+            #.....#
+            .#...#.
+            ..#.#..
+            ...#...
+            ..#.#..
+            .#...#.
+            #.....#",
+        Icon::Warning => "
+            ...#...
+            ..###..
+            ..#.#..
+            .##.##.
+            .#####.
+            ###.###
+            #######",
+        Icon::Play => "
+            #......
+            ###....
+            #####..
+            #######
+            #####..
+            ###....
+            #......",
+        Icon::Pause => "
+            ##...##
+            ##...##
+            ##...##
+            ##...##
+            ##...##
+            ##...##
+            ##...##",
+        Icon::Battery(_) | Icon::Wifi(_) => return None
+    };
+
+    Some(art)
+}
+
+#[test]
+fn draws_every_icon() {
+    let (x, o) = (true, false);
+
+    for size in [IconSize::Small, IconSize::Medium, IconSize::Large] {
+        let font = Font::icons(size);
+
+        for icon in Icon::all() {
+            assert!(font.has_glyph(&icon.char()), "{icon:?} missing at {size:?}");
+        }
+    }
+
+    let small = Font::icons(IconSize::Small);
+
+    assert_eq!(small.get_glyph(&Icon::Battery(2).char()), &Glyph::from(vec![
+        x, x, x, x, o,
+        x, x, o, x, x,
+        x, x, o, x, x,
+        x, x, o, x, x,
+        x, x, x, x, o,
+    ]));
+
+    // Every level can be told apart, at every size.
+    for size in [IconSize::Small, IconSize::Medium, IconSize::Large] {
+        let font = Font::icons(size);
+
+        for level in 0..Icon::MAX_BATTERY {
+            assert_ne!(
+                font.get_glyph(&Icon::Battery(level).char()),
+                font.get_glyph(&Icon::Battery(level + 1).char()),
+                "Battery({level}) at {size:?}"
+            );
+        }
+    }
+
+    assert_eq!(small.get_glyph(&Icon::Wifi(Icon::MAX_WIFI).char()), &Glyph::from(vec![
+        x, x, x, o, o,
+        o, o, x, x, o,
+        x, x, o, x, x,
+        o, o, x, o, x,
+        x, o, x, o, x,
+    ]));
+
+    for icon in FIXED_ICONS {
+        assert!(Glyph::from_art(small_icon_art(icon).unwrap_or_default(), &Size::new(5, 5)).is_ok(), "{icon:?}");
+        assert!(Glyph::from_art(medium_icon_art(icon).unwrap_or_default(), &Size::new(7, 7)).is_ok(), "{icon:?}");
+    }
+
+    // Levels past the maximum are drawn as the maximum.
+    assert_eq!(Icon::Battery(9).char(), Icon::Battery(Icon::MAX_BATTERY).char());
+}
@@ -0,0 +1,129 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{ascii::Ascii, Font, Glyph};
+
+/// Problems found in the glyph table of a font, for the printable `Ascii` characters.
+/// Only the font's own glyphs are checked, not the ones from its fallbacks.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FontReport {
+    /// Characters without a glyph.
+    pub missing: Vec<Ascii>,
+    /// Characters whose glyph doesn't have a single point set.
+    pub blank: Vec<Ascii>,
+    /// Characters whose glyph doesn't have as many points as the font's glyph size.
+    pub malformed: Vec<Ascii>,
+    /// Characters drawn with the same bitmap, other than the upper and lowercase of a letter.
+    pub shared: Vec<Vec<Ascii>>
+}
+
+impl FontReport {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.blank.is_empty() && self.malformed.is_empty() && self.shared.is_empty()
+    }
+}
+
+impl Font {
+    /// Every character with a glyph of its own in this font, in order.
+    pub fn chars(&self) -> Vec<char> {
+        let mut chars: Vec<_> = self.glyphs.keys().copied().collect();
+        chars.sort();
+
+        chars
+    }
+
+    pub fn report(&self) -> FontReport {
+        let mut report = FontReport::default();
+        let mut bitmaps: Vec<(&Glyph, Vec<Ascii>)> = vec![];
+
+        let point_count = self.size.width * self.size.height;
+
+        // Spaces are not drawn with a glyph.
+        let printable = (b'!'..=b'~').map(Ascii);
+
+        for ascii in printable {
+            let Some(glyph) = self.glyphs.get(&ascii.char()) else {
+                report.missing.push(ascii);
+                continue;
+            };
+
+            if glyph.0.len() != point_count {
+                report.malformed.push(ascii);
+            } else if glyph.0.iter().all(|point| point.is_empty()) {
+                report.blank.push(ascii);
+            }
+
+            match bitmaps.iter_mut().find(|(bitmap, _)| *bitmap == glyph) {
+                Some((_, chars)) => chars.push(ascii),
+                None => bitmaps.push((glyph, vec![ascii]))
+            }
+        }
+
+        report.shared = bitmaps
+            .into_iter()
+            .map(|(_, chars)| chars)
+            .filter(|chars| chars.len() > 1 && !is_single_letter(chars))
+            .collect();
+
+        report
+    }
+}
+
+/// Whether all the characters are the same letter in upper or lowercase.
+fn is_single_letter(chars: &[Ascii]) -> bool {
+    let mut letters: HashMap<u8, usize> = HashMap::new();
+
+    for ascii in chars {
+        if !ascii.u8().is_ascii_alphabetic() {
+            return false;
+        }
+
+        *letters.entry(ascii.u8().to_ascii_lowercase()).or_default() += 1;
+    }
+
+    letters.len() == 1
+}
+
+impl Display for FontReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |chars: &[Ascii]| chars.iter().map(|ascii| ascii.char()).collect::<String>();
+
+        if self.is_empty() {
+            return f.write_str("Every printable character has a glyph of its own.");
+        }
+
+        if !self.missing.is_empty() {
+            writeln!(f, "Missing: {}", list(&self.missing))?;
+        }
+
+        if !self.blank.is_empty() {
+            writeln!(f, "Blank: {}", list(&self.blank))?;
+        }
+
+        if !self.malformed.is_empty() {
+            writeln!(f, "Wrong size: {}", list(&self.malformed))?;
+        }
+
+        for chars in &self.shared {
+            writeln!(f, "Same glyph: {}", list(chars))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn reports_three_by_three_problems() {
+    let report = Font::three_by_three().report();
+
+    let chars = |text: &str| -> Vec<Ascii> { text.bytes().map(Ascii).collect() };
+
+    assert!(report.missing.contains(&Ascii(b'#')));
+    assert!(!report.missing.contains(&Ascii(b'5')));
+
+    assert!(report.shared.contains(&chars("5Ss")));
+    assert!(report.shared.contains(&chars("2Zz")));
+    assert!(!report.shared.contains(&chars("Aa")));
+
+    let four_by_four = Font::four_by_four().report();
+    assert!(four_by_four.malformed.contains(&Ascii(b'1')));
+}
@@ -1,95 +1,201 @@
+pub mod art;
 pub mod ascii;
+pub mod classic;
 pub mod font_calculations;
+pub mod icons;
+pub mod inspection;
+pub mod psf;
+pub mod registry;
+pub mod rich_text;
+pub mod style;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::layout::geometry::Size;
-use ascii::Ascii;
 use once_cell::sync::Lazy;
 
-/// A grid of points (bool) that determine how a glyph should be drawn.
-/// `true` is a pixel to fill in, `false` represents an empty space in the grid.
-#[derive(PartialEq, Debug)]
-pub struct Glyph(Vec<bool>);
+/// A grid of points that determine how a glyph should be drawn.
+/// Glyphs built from `bool`s fill in the `true` points with the content of the text.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Glyph(Vec<GlyphPoint>);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum GlyphPoint {
+    #[default]
+    Empty,
+    /// Drawn with the content of the text.
+    Ink,
+    /// Drawn with an entry of the text's palette, for multi-color glyphs like icons.
+    /// Indices the palette doesn't have are drawn with the content of the text.
+    Palette(u8)
+}
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Font {
     pub name: String,
-    glyphs: HashMap<Ascii, Glyph>,
+    glyphs: Arc<HashMap<char, Glyph>>,
     unknown_glyph: Glyph,
     size: Size,
     space_width: usize,
     line_spacing: usize,
-    character_spacing: usize
+    character_spacing: usize,
+    /// Rows of the glyphs that go below the baseline.
+    descent: usize,
+    /// Where a tab moves the text to, from the start of the line.
+    tab_stops: Vec<usize>,
+    /// Distance between the tab stops after the last one in `tab_stops`.
+    tab_width: usize,
+    /// Fonts to take glyphs from when this one doesn't have them, in order of preference.
+    fallbacks: Vec<Arc<Font>>
 }
 
-static THREE_BY_THREE_MONO: Lazy<Font> = Lazy::new(|| {
+/// How many spaces fit between the default tab stops.
+const TAB_SPACES: usize = 4;
+
+static THREE_BY_THREE_MONO: Lazy<Arc<Font>> = Lazy::new(|| {
     let square_size = 3;
     let character_spacing = 1;
     let space_width = character_spacing * 2 + square_size;
 
-    Font {
+    Arc::new(Font {
         name: "3x3Mono".to_string(),
-        glyphs: three_by_three_glyphs(),
+        glyphs: Arc::new(three_by_three_glyphs()),
         size: Size::new(square_size, square_size),
         unknown_glyph: three_by_three_unknown_glyph(),
         space_width,
         line_spacing: 1,
-        character_spacing
-    }
+        character_spacing,
+        descent: 0,
+        tab_stops: vec![],
+        tab_width: space_width * TAB_SPACES,
+        fallbacks: vec![]
+    })
 });
 
-static FOUR_BY_FOUR_MONO: Lazy<Font> = Lazy::new(|| {
+static FOUR_BY_FOUR_MONO: Lazy<Arc<Font>> = Lazy::new(|| {
     let square_size = 4;
     let character_spacing = 1;
     let space_width = character_spacing * 2 + square_size;
 
-    Font {
+    Arc::new(Font {
         name: "4x4Mono".to_string(),
-        glyphs: four_by_four_glyphs(),
+        glyphs: Arc::new(four_by_four_glyphs()),
         size: Size::new(square_size, square_size),
         unknown_glyph: four_by_four_unknown_glyph(),
         space_width,
         line_spacing: 1,
-        character_spacing
-    }
+        character_spacing,
+        descent: 0,
+        tab_stops: vec![],
+        tab_width: space_width * TAB_SPACES,
+        fallbacks: vec![]
+    })
 });
 
-static FOUR_BY_FIVE_MONO: Lazy<Font> = Lazy::new(|| {
+static FOUR_BY_FIVE_MONO: Lazy<Arc<Font>> = Lazy::new(|| {
     let character_width = 4;
     let character_height = 5;
     let character_spacing = 1;
     let space_width = character_spacing * 2 + character_width;
 
-    Font {
+    Arc::new(Font {
         name: "4x5Mono".to_string(),
-        glyphs: four_by_five_glyphs(),
+        glyphs: Arc::new(four_by_five_glyphs()),
         size: Size::new(character_width, character_height),
         unknown_glyph: four_by_five_unknown_glyph(),
         space_width,
         line_spacing: 1,
-        character_spacing
-    }
+        character_spacing,
+        descent: 0,
+        tab_stops: vec![],
+        tab_width: space_width * TAB_SPACES,
+        fallbacks: vec![]
+    })
 });
 
 impl Font {
-    pub fn three_by_three() -> &'static Self {
+    pub fn three_by_three() -> Arc<Self> {
         // TODO: Hardcoded 3x3 font
-        &THREE_BY_THREE_MONO
+        THREE_BY_THREE_MONO.clone()
     }
 
-    pub fn four_by_four() -> &'static Self {
+    pub fn four_by_four() -> Arc<Self> {
         // TODO: Hardcoded 4x4 font
-        &FOUR_BY_FOUR_MONO
+        FOUR_BY_FOUR_MONO.clone()
     }
 
-    pub fn four_by_five() -> &'static Self {
+    pub fn four_by_five() -> Arc<Self> {
         // TODO: Hardcoded 4x5 font
-        &FOUR_BY_FIVE_MONO
+        FOUR_BY_FIVE_MONO.clone()
+    }
+
+    /// A font made of the given glyphs, all of them `size` points big. Missing glyphs are left blank.
+    /// Icon fonts built this way can be used as a fallback of a text font, to mix icons in with the text.
+    pub fn new(name: &str, size: Size, glyphs: HashMap<char, Glyph>) -> Font {
+        let character_spacing = 1;
+
+        Font {
+            name: name.to_string(),
+            glyphs: Arc::new(glyphs),
+            unknown_glyph: Glyph(vec![GlyphPoint::Empty; size.width * size.height]),
+            space_width: size.width,
+            line_spacing: 1,
+            character_spacing,
+            descent: 0,
+            tab_stops: vec![],
+            tab_width: size.width * TAB_SPACES,
+            fallbacks: vec![],
+            size
+        }
+    }
+
+    /// Returns a copy of this font with `glyph` drawn for `c`.
+    /// The glyph needs to have as many points as the font's glyph size.
+    pub fn with_glyph(mut self, c: char, glyph: Glyph) -> Font {
+        Arc::make_mut(&mut self.glyphs).insert(c, glyph);
+
+        self
+    }
+
+    /// Returns a copy of this font that takes missing glyphs from `fallback`.
+    /// Fallbacks are tried in the order they were added.
+    pub fn with_fallback<F: Into<Arc<Font>>>(mut self, fallback: F) -> Font {
+        self.fallbacks.push(fallback.into());
+
+        self
+    }
+
+    pub fn fallbacks(&self) -> &[Arc<Font>] {
+        &self.fallbacks
+    }
+
+    pub fn has_glyph(&self, c: &char) -> bool {
+        self.find_glyph(c).is_some()
+    }
+
+    pub fn get_glyph(&self, c: &char) -> &Glyph {
+        self.find_glyph(c)
+            .map(|(glyph, _)| glyph)
+            .unwrap_or(&self.unknown_glyph)
     }
 
-    pub fn get_glyph(&self, c: &Ascii) -> &Glyph {
-        self.glyphs.get(c).unwrap_or(&self.unknown_glyph)
+    /// The font in the fallback chain that draws `c`, or this one when none has it.
+    pub fn glyph_font(&self, c: char) -> &Font {
+        self.find_glyph(&c)
+            .map(|(_, font)| font)
+            .unwrap_or(self)
+    }
+
+    /// Looks for the glyph in this font, then through the fallback chain.
+    /// The font returned is the one that had the glyph.
+    fn find_glyph(&self, c: &char) -> Option<(&Glyph, &Font)> {
+        if let Some(glyph) = self.glyphs.get(c) {
+            return Some((glyph, self));
+        }
+
+        self.fallbacks
+            .iter()
+            .find_map(|fallback| fallback.find_glyph(c))
     }
 
     pub fn space_width(&self) -> usize {
@@ -104,14 +210,147 @@ impl Font {
         self.character_spacing
     }
 
+    /// The first tab stop after `x`. Past the explicit stops, there's one every `tab_width` points.
+    pub fn next_tab_stop(&self, x: usize) -> usize {
+        if let Some(stop) = self.tab_stops.iter().find(|stop| **stop > x) {
+            return *stop;
+        }
+
+        let last_stop = self.tab_stops.last().copied().unwrap_or(0);
+        let tab_width = self.tab_width.max(1);
+
+        last_stop + ((x - last_stop) / tab_width + 1) * tab_width
+    }
+
+    pub fn with_character_spacing(mut self, character_spacing: usize) -> Font {
+        self.character_spacing = character_spacing;
+
+        self
+    }
+
+    pub fn with_line_spacing(mut self, line_spacing: usize) -> Font {
+        self.line_spacing = line_spacing;
+
+        self
+    }
+
+    pub fn with_space_width(mut self, space_width: usize) -> Font {
+        self.space_width = space_width;
+
+        self
+    }
+
+    /// Returns a copy of this font with tab stops every `tab_width` points.
+    pub fn with_tab_width(mut self, tab_width: usize) -> Font {
+        self.tab_stops = vec![];
+        self.tab_width = tab_width;
+
+        self
+    }
+
+    /// Returns a copy of this font with tabs stopping at `stops` (in points from the start of the line),
+    /// followed by the regular ones every `tab_width` points.
+    pub fn with_tab_stops(mut self, mut stops: Vec<usize>) -> Font {
+        stops.sort();
+        stops.dedup();
+        self.tab_stops = stops;
+
+        self
+    }
+
     pub fn default_glyph(&self) -> &Glyph {
         &self.unknown_glyph
     }
 
-    pub fn size(&self, _c: Ascii) -> &Size {
+    pub fn size(&self, c: char) -> &Size {
         // TODO: Handle non-monospaced fonts (by using the actual character)
+        &self.glyph_font(c).size
+    }
+
+    /// Rows of the glyphs above the baseline.
+    pub fn ascent(&self) -> usize {
+        self.size.height.saturating_sub(self.descent)
+    }
+
+    /// Rows of the glyphs below the baseline.
+    pub fn descent(&self) -> usize {
+        self.descent
+    }
+
+    /// Returns a copy of this font whose glyphs go `descent` rows below the baseline.
+    pub fn with_descent(mut self, descent: usize) -> Font {
+        self.descent = descent.min(self.size.height);
+
+        self
+    }
+
+    /// The size of this font's own glyphs, ignoring fallbacks.
+    pub fn glyph_size(&self) -> &Size {
         &self.size
     }
+
+    /// A copy of this font (and its fallbacks) with every glyph and spacing multiplied by `factor`.
+    /// Each point of a glyph becomes a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Font {
+        assert!(factor > 0, "Fonts can't be scaled to nothing");
+
+        let mut font = self.with_transformed_glyphs(
+            &format!("@{factor}x"),
+            &|glyph, size| glyph.scale(size, factor),
+            &|size| size.clone().scaled(factor)
+        );
+
+        font.apply_to_chain(&|font| {
+            font.space_width *= factor;
+            font.line_spacing *= factor;
+            font.character_spacing *= factor;
+            font.descent *= factor;
+            font.tab_width *= factor;
+            font.tab_stops.iter_mut().for_each(|stop| *stop *= factor);
+        });
+
+        font
+    }
+
+    /// A copy of this font (and its fallbacks) where every glyph went through `transform_glyph`,
+    /// which turns glyphs of the font's size into glyphs of the size given by `transform_size`.
+    pub(crate) fn with_transformed_glyphs<G, S>(&self, name_suffix: &str, transform_glyph: &G, transform_size: &S) -> Font
+    where
+        G: Fn(&Glyph, &Size) -> Glyph,
+        S: Fn(&Size) -> Size
+    {
+        let glyphs = self.glyphs
+            .iter()
+            .map(|(c, glyph)| (*c, transform_glyph(glyph, &self.size)))
+            .collect();
+
+        Font {
+            name: format!("{}{}", self.name, name_suffix),
+            glyphs: Arc::new(glyphs),
+            unknown_glyph: transform_glyph(&self.unknown_glyph, &self.size),
+            size: transform_size(&self.size),
+            space_width: self.space_width,
+            line_spacing: self.line_spacing,
+            character_spacing: self.character_spacing,
+            descent: self.descent,
+            tab_stops: self.tab_stops.clone(),
+            tab_width: self.tab_width,
+            fallbacks: self.fallbacks
+                .iter()
+                .map(|fallback| Arc::new(fallback.with_transformed_glyphs(name_suffix, transform_glyph, transform_size)))
+                .collect()
+        }
+    }
+
+    /// Changes this font and every font in its fallback chain.
+    /// Fallbacks shared with other fonts are copied before changing them.
+    pub(crate) fn apply_to_chain<F: Fn(&mut Font)>(&mut self, change: &F) {
+        change(self);
+
+        for fallback in self.fallbacks.iter_mut() {
+            Arc::make_mut(fallback).apply_to_chain(change);
+        }
+    }
 }
 
 // TODO: use this. For now, will only use simple 3x3 font
@@ -119,12 +358,11 @@ pub enum FontType {
     Bitmap
 }
 
-fn three_by_three_glyphs() -> HashMap<Ascii, Glyph> {
-    let mut glyphs: HashMap<Ascii, Glyph> = Default::default();
+fn three_by_three_glyphs() -> HashMap<char, Glyph> {
+    let mut glyphs: HashMap<char, Glyph> = Default::default();
     let mut insert = |chars: Vec<char>, points: Vec<bool>| {
         for c in chars {
-            let Ok(ascii) = c.try_into() else { continue };
-            glyphs.insert(ascii, Glyph(points.clone()));
+            glyphs.insert(c, Glyph::from(points.clone()));
         }
     };
 
@@ -182,19 +420,29 @@ fn three_by_three_glyphs() -> HashMap<Ascii, Glyph> {
     insert(vec!['+'], vec![false, true, false, true, true, true, false, true, false]);
     insert(vec!['-'], vec![false, false, false, true, true, true, false, false, false]);
 
+    insert(vec!['←'], vec![false, true, false, true, false, false, false, true, false]);
+    insert(vec!['→'], vec![false, true, false, false, false, true, false, true, false]);
+    insert(vec!['↑'], vec![false, true, false, true, false, true, false, false, false]);
+    insert(vec!['↓'], vec![false, false, false, true, false, true, false, true, false]);
+    insert(vec!['¡'], vec![false, true, false, false, false, false, true, true, true]);
+    insert(vec!['ß'], vec![true, true, false, true, true, true, true, true, true]);
+
+    // Too small for diacritics. Accented letters are drawn as the letter alone.
+    insert_unaccented_letters(&mut glyphs);
+    insert_box_drawing(&mut glyphs, &Size::new(3, 3));
+
     glyphs
 }
 
 fn three_by_three_unknown_glyph() -> Glyph {
-    Glyph(vec![false, true, false, true, false, true, false, true, false])
+    Glyph::from(vec![false, true, false, true, false, true, false, true, false])
 }
 
-fn four_by_four_glyphs() -> HashMap<Ascii, Glyph> {
-    let mut glyphs: HashMap<Ascii, Glyph> = Default::default();
+fn four_by_four_glyphs() -> HashMap<char, Glyph> {
+    let mut glyphs: HashMap<char, Glyph> = Default::default();
     let mut insert = |chars: Vec<char>, points: Vec<bool>| {
         for c in chars {
-            let Ok(ascii) = c.try_into() else { continue };
-            glyphs.insert(ascii, Glyph(points.clone()));
+            glyphs.insert(c, Glyph::from(points.clone()));
         }
     };
 
@@ -251,23 +499,24 @@ fn four_by_four_glyphs() -> HashMap<Ascii, Glyph> {
     // insert(vec!['+'], vec![]);
     // insert(vec!['-'], vec![]);
 
+    insert_unaccented_letters(&mut glyphs);
+    insert_box_drawing(&mut glyphs, &Size::new(4, 4));
+
     glyphs
 }
 
 fn four_by_four_unknown_glyph() -> Glyph {
-    Glyph(vec![true, false, true, false, false, true, false, true, true, false, true, false, false, true, false, true,])
+    Glyph::from(vec![true, false, true, false, false, true, false, true, true, false, true, false, false, true, false, true,])
 }
 
-fn four_by_five_glyphs() -> HashMap<Ascii, Glyph> {
-    let mut glyphs: HashMap<Ascii, Glyph> = Default::default();
+fn four_by_five_glyphs() -> HashMap<char, Glyph> {
+    let mut glyphs: HashMap<char, Glyph> = Default::default();
 
     let map_4x4_to_4x5 = |glyph: &Glyph| {
-        let mut new_points = vec![false, false, false, false];
+        let mut new_points = vec![GlyphPoint::Empty; 4];
         new_points.extend_from_slice(&glyph.0);
 
-        let new_glyph = Glyph(new_points);
-
-        new_glyph
+        Glyph(new_points)
     };
 
     // The 4x5 font uses 4x4 glyphs as the lowercase (and fallback for any non existent)
@@ -276,8 +525,7 @@ fn four_by_five_glyphs() -> HashMap<Ascii, Glyph> {
 
     let mut insert = |chars: Vec<char>, points: Vec<bool>| {
         for c in chars {
-            let Ok(ascii) = c.try_into() else { continue };
-            glyphs.insert(ascii, Glyph(points.clone()));
+            glyphs.insert(c, Glyph::from(points.clone()));
         }
     };
 
@@ -313,26 +561,166 @@ fn four_by_five_glyphs() -> HashMap<Ascii, Glyph> {
     insert(vec!['!'], vec![false, false, true, false, false, false, true, false, false, false, true, false, false, false, false, false, false, false, true, false]);
     insert(vec!['?'], vec![false, true, true, false, true, false, false, true, false, false, true, false, false, false, false, false, false, false, true, false]);
 
+    insert(vec!['←'], vec![false, false, false, false, false, true, false, false, true, true, true, true, false, true, false, false, false, false, false, false]);
+    insert(vec!['→'], vec![false, false, false, false, false, false, true, false, true, true, true, true, false, false, true, false, false, false, false, false]);
+    insert(vec!['↑'], vec![false, true, false, false, true, true, true, false, false, true, false, false, false, true, false, false, false, true, false, false]);
+    insert(vec!['↓'], vec![false, true, false, false, false, true, false, false, false, true, false, false, true, true, true, false, false, true, false, false]);
+    insert(vec!['ß'], vec![false, true, true, false, true, false, false, true, true, false, true, false, true, false, false, true, true, false, true, false]);
+    insert(vec!['¡'], vec![false, false, true, false, false, false, false, false, false, false, true, false, false, false, true, false, false, false, true, false]);
+    insert(vec!['¿'], vec![false, true, false, false, false, false, false, false, false, true, false, false, true, false, false, true, false, true, true, false]);
+
+    // The lowercase letters leave the top row empty, which is where the diacritics go.
+    let lowercase = four_by_four_glyphs();
+    for (letter, base, diacritic) in LATIN_1_LETTERS {
+        let Some(base_glyph) = lowercase.get(&base) else { continue };
+
+        let glyph = match diacritic.four_point_mark() {
+            Some(mark) => {
+                let mut points: Vec<_> = mark.iter().map(|point| GlyphPoint::from(*point)).collect();
+                points.extend_from_slice(&base_glyph.0);
+
+                Glyph(points)
+            }
+            None => map_4x4_to_4x5(base_glyph)
+        };
+
+        glyphs.insert(letter, glyph);
+
+        // Capitals take up the whole glyph, so there's no room for the mark. They're drawn unaccented.
+        let Some(capital) = glyphs.get(&base.to_ascii_uppercase()).cloned() else { continue };
+        for c in letter.to_uppercase() {
+            glyphs.insert(c, capital.clone());
+        }
+    }
+
+    insert_box_drawing(&mut glyphs, &Size::new(4, 5));
 
     glyphs
 }
 
 fn four_by_five_unknown_glyph() -> Glyph {
-    Glyph(vec![true, false, true, false, false, true, false, true, true, false, true, false, false, true, false, true, true, false, true, false])
+    Glyph::from(vec![true, false, true, false, false, true, false, true, true, false, true, false, false, true, false, true, true, false, true, false])
+}
+
+#[derive(Clone, Copy)]
+enum Diacritic {
+    Grave,
+    Acute,
+    Circumflex,
+    Tilde,
+    Diaeresis,
+    Ring,
+    Cedilla
+}
+
+impl Diacritic {
+    /// The mark as a row of 4 points, to draw on top of a 4x4 letter.
+    /// Marks that go below the letter have no representation.
+    fn four_point_mark(self) -> Option<[bool; 4]> {
+        match self {
+            Diacritic::Grave => Some([false, true, false, false]),
+            Diacritic::Acute => Some([false, false, true, false]),
+            Diacritic::Circumflex | Diacritic::Ring => Some([false, true, true, false]),
+            Diacritic::Tilde => Some([true, true, true, true]),
+            Diacritic::Diaeresis => Some([true, false, false, true]),
+            Diacritic::Cedilla => None
+        }
+    }
+}
+
+/// The lowercase Latin-1 letters with diacritics, and what they're made of.
+/// Their uppercase versions are drawn as the plain capital letter, without the mark,
+/// so `Ä`, `Ö` or `Ñ` look like `A`, `O` and `N`.
+const LATIN_1_LETTERS: [(char, char, Diacritic); 27] = [
+    ('à', 'a', Diacritic::Grave),
+    ('á', 'a', Diacritic::Acute),
+    ('â', 'a', Diacritic::Circumflex),
+    ('ã', 'a', Diacritic::Tilde),
+    ('ä', 'a', Diacritic::Diaeresis),
+    ('å', 'a', Diacritic::Ring),
+    ('ç', 'c', Diacritic::Cedilla),
+    ('è', 'e', Diacritic::Grave),
+    ('é', 'e', Diacritic::Acute),
+    ('ê', 'e', Diacritic::Circumflex),
+    ('ë', 'e', Diacritic::Diaeresis),
+    ('ì', 'i', Diacritic::Grave),
+    ('í', 'i', Diacritic::Acute),
+    ('î', 'i', Diacritic::Circumflex),
+    ('ï', 'i', Diacritic::Diaeresis),
+    ('ñ', 'n', Diacritic::Tilde),
+    ('ò', 'o', Diacritic::Grave),
+    ('ó', 'o', Diacritic::Acute),
+    ('ô', 'o', Diacritic::Circumflex),
+    ('õ', 'o', Diacritic::Tilde),
+    ('ö', 'o', Diacritic::Diaeresis),
+    ('ù', 'u', Diacritic::Grave),
+    ('ú', 'u', Diacritic::Acute),
+    ('û', 'u', Diacritic::Circumflex),
+    ('ü', 'u', Diacritic::Diaeresis),
+    ('ý', 'y', Diacritic::Acute),
+    ('ÿ', 'y', Diacritic::Diaeresis),
+];
+
+fn insert_unaccented_letters(glyphs: &mut HashMap<char, Glyph>) {
+    for (letter, base, _) in LATIN_1_LETTERS {
+        let Some(base_glyph) = glyphs.get(&base).cloned() else { continue };
+        let uppercase_glyph = glyphs.get(&base.to_ascii_uppercase()).cloned().unwrap_or(base_glyph.clone());
+
+        for c in letter.to_uppercase() {
+            glyphs.insert(c, uppercase_glyph.clone());
+        }
+
+        glyphs.insert(letter, base_glyph);
+    }
+}
+
+/// The box drawing characters, and the sides (up, right, down, left) their lines reach.
+const BOX_DRAWING: [(char, [bool; 4]); 11] = [
+    ('─', [false, true, false, true]),
+    ('│', [true, false, true, false]),
+    ('┌', [false, true, true, false]),
+    ('┐', [false, false, true, true]),
+    ('└', [true, true, false, false]),
+    ('┘', [true, false, false, true]),
+    ('├', [true, true, true, false]),
+    ('┤', [true, false, true, true]),
+    ('┬', [false, true, true, true]),
+    ('┴', [true, true, false, true]),
+    ('┼', [true, true, true, true]),
+];
+
+fn insert_box_drawing(glyphs: &mut HashMap<char, Glyph>, size: &Size) {
+    let center_x = (size.width - 1) / 2;
+    let center_y = (size.height - 1) / 2;
+
+    for (c, [up, right, down, left]) in BOX_DRAWING {
+        let mut points = vec![false; size.width * size.height];
+
+        for y in 0..size.height {
+            for x in 0..size.width {
+                let vertical = x == center_x && ((up && y <= center_y) || (down && y >= center_y));
+                let horizontal = y == center_y && ((left && x <= center_x) || (right && x >= center_x));
+
+                points[y * size.width + x] = vertical || horizontal;
+            }
+        }
+
+        glyphs.insert(c, Glyph::from(points));
+    }
 }
 
 impl Glyph {
     pub fn scale(&self, original_size: &Size, scale: usize) -> Glyph {
-        let mut new_points = vec![false; original_size.width * scale * original_size.height * scale];
-
         let new_size = original_size.clone().scaled(scale);
 
+        let mut new_points = vec![GlyphPoint::Empty; new_size.width * new_size.height];
+
         for j in 0..new_size.height {
             for i in 0..new_size.width {
-                let new_index = j * original_size.width + i;
+                let new_index = j * new_size.width + i;
 
                 let original_i = i / scale;
-                let original_j = i / scale;
+                let original_j = j / scale;
 
                 let original_index = original_j * original_size.width + original_i;
 
@@ -345,8 +733,43 @@ impl Glyph {
         Glyph(new_points)
     }
 
-    pub fn map<V, F: Fn(bool) -> V>(&self, transform: F) -> Vec<V> {
-        self.0.iter().map(| b| transform(*b)).collect()
+    pub fn new(points: Vec<GlyphPoint>) -> Glyph {
+        Glyph(points)
+    }
+
+    pub fn points(&self) -> &[GlyphPoint] {
+        &self.0
+    }
+
+    pub fn map<V, F: Fn(GlyphPoint) -> V>(&self, transform: F) -> Vec<V> {
+        self.0.iter().map(|point| transform(*point)).collect()
+    }
+
+    /// The bitmap of the glyph, with its ink drawn with `ink` and palette points taken from `palette`.
+    pub fn paint<Content: Clone>(&self, ink: &Content, palette: &[Content]) -> Vec<Option<Content>> {
+        self.map(|point| match point {
+            GlyphPoint::Empty => None,
+            GlyphPoint::Ink => Some(ink.clone()),
+            GlyphPoint::Palette(index) => Some(palette.get(index as usize).unwrap_or(ink).clone())
+        })
+    }
+}
+
+impl GlyphPoint {
+    pub fn is_empty(&self) -> bool {
+        *self == GlyphPoint::Empty
+    }
+}
+
+impl From<bool> for GlyphPoint {
+    fn from(value: bool) -> Self {
+        if value { GlyphPoint::Ink } else { GlyphPoint::Empty }
+    }
+}
+
+impl From<Vec<bool>> for Glyph {
+    fn from(value: Vec<bool>) -> Self {
+        Glyph(value.into_iter().map(GlyphPoint::from).collect())
     }
 }
 
@@ -368,4 +791,58 @@ impl<K: Eq + std::hash::Hash + Copy, V> HashMapConvert for HashMap<K, V> {
             other.insert(*key, value);
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn scale_font_glyphs() {
+    let font = Font::three_by_three().scaled(2);
+
+    assert_eq!(font.size('1'), &Size::new(6, 6));
+    assert_eq!(font.space_width(), 10);
+
+    let (x, o) = (true, false);
+    assert_eq!(font.get_glyph(&'1'), &Glyph::from(vec![
+        x, x, x, x, o, o,
+        x, x, x, x, o, o,
+        o, o, x, x, o, o,
+        o, o, x, x, o, o,
+        x, x, x, x, x, x,
+        x, x, x, x, x, x,
+    ]));
+}
+
+#[test]
+fn paints_multi_color_glyphs() {
+    use GlyphPoint::*;
+
+    let heart = Glyph::new(vec![
+        Palette(0), Empty, Palette(0),
+        Palette(0), Palette(1), Palette(0),
+        Empty, Ink, Empty,
+    ]);
+
+    let icons = Font::new("icons", Size::new(3, 3), HashMap::new()).with_glyph('♥', heart.clone());
+    let font = Font::three_by_three().as_ref().clone().with_fallback(icons);
+
+    assert_eq!(font.get_glyph(&'♥'), &heart);
+
+    // The second palette entry is missing, so it's drawn like the ink.
+    assert_eq!(heart.paint(&1u8, &[2]), vec![
+        Some(2), None, Some(2),
+        Some(2), Some(1), Some(2),
+        None, Some(1), None,
+    ]);
+}
+
+#[test]
+fn draws_accented_capitals_as_capitals() {
+    let font = Font::four_by_five();
+
+    assert_eq!(font.get_glyph(&'Ä'), font.get_glyph(&'A'));
+    assert_eq!(font.get_glyph(&'Á'), font.get_glyph(&'A'));
+    assert_ne!(font.get_glyph(&'ä'), font.get_glyph(&'a'));
+    assert_ne!(font.get_glyph(&'Ä'), font.get_glyph(&'ä'));
+
+    let small = Font::three_by_three();
+    assert_eq!(small.get_glyph(&'É'), small.get_glyph(&'E'));
+}
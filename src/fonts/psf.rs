@@ -0,0 +1,339 @@
+use std::{collections::HashMap, error::Error, fmt::Display, path::Path, sync::Arc};
+
+use crate::layout::geometry::Size;
+
+use super::{Font, Glyph, TAB_SPACES};
+
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF1_MODE_512: u8 = 0x01;
+const PSF1_MODE_HAS_TABLE: u8 = 0x02;
+const PSF1_MODE_HAS_SEQUENCES: u8 = 0x04;
+const PSF1_SEPARATOR: u16 = 0xFFFF;
+const PSF1_START_SEQUENCE: u16 = 0xFFFE;
+
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+const PSF2_HAS_UNICODE_TABLE: u32 = 0x01;
+const PSF2_SEPARATOR: u8 = 0xFF;
+const PSF2_START_SEQUENCE: u8 = 0xFE;
+/// The size of the fields every PSF2 header has. Newer versions may add more after them.
+const PSF2_HEADER_SIZE: usize = 32;
+
+#[derive(Debug)]
+pub enum PsfError {
+    Io(std::io::Error),
+    UnknownFormat,
+    UnexpectedEnd,
+    InvalidHeader(&'static str),
+    InvalidUnicodeTable(usize)
+}
+
+impl Error for PsfError {}
+
+impl Display for PsfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self))
+    }
+}
+
+impl From<std::io::Error> for PsfError {
+    fn from(value: std::io::Error) -> Self {
+        PsfError::Io(value)
+    }
+}
+
+/// The glyph bitmaps and character mappings read from a PSF file, before turning them into a `Font`.
+struct PsfContents {
+    size: Size,
+    glyphs: Vec<Glyph>,
+    /// For every glyph, the characters it represents. `None` when the file has no unicode table.
+    mappings: Option<Vec<Vec<char>>>
+}
+
+impl Font {
+    /// Reads a PSF1 or PSF2 console font from disk.
+    pub fn open_psf<P: AsRef<Path>>(path: P) -> Result<Font, PsfError> {
+        let name = path.as_ref()
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let bytes = std::fs::read(path)?;
+
+        Font::from_psf(&name, &bytes)
+    }
+
+    /// Builds a font from the contents of a PSF1 or PSF2 file.
+    /// If the file has a unicode table it's used to map characters to glyphs,
+    /// otherwise each glyph index is taken as the character code.
+    pub fn from_psf(name: &str, bytes: &[u8]) -> Result<Font, PsfError> {
+        let contents = if bytes.starts_with(&PSF2_MAGIC) {
+            read_psf2(bytes)?
+        } else if bytes.starts_with(&PSF1_MAGIC) {
+            read_psf1(bytes)?
+        } else {
+            return Err(PsfError::UnknownFormat);
+        };
+
+        let PsfContents { size, glyphs, mappings } = contents;
+
+        let mut glyph_table: HashMap<char, Glyph> = Default::default();
+        let mut replacement_glyph = None;
+
+        for (index, glyph) in glyphs.iter().enumerate() {
+            let chars = match &mappings {
+                Some(mappings) => mappings[index].clone(),
+                None => char::from_u32(index as u32).into_iter().collect()
+            };
+
+            for c in chars {
+                if c == char::REPLACEMENT_CHARACTER {
+                    replacement_glyph = Some(glyph.clone());
+                }
+
+                glyph_table.entry(c).or_insert_with(|| glyph.clone());
+            }
+        }
+
+        let unknown_glyph = replacement_glyph
+            .or_else(|| glyph_table.get(&'?').cloned())
+            .unwrap_or_else(|| Glyph::from(vec![false; size.width * size.height]));
+
+        // PSF files don't store a baseline, so it's taken from the bottom of a glyph that sits on it.
+        let descent = ['H', 'x', '0']
+            .iter()
+            .find_map(|c| glyph_table.get(c))
+            .and_then(|glyph| glyph.bottom_row(&size))
+            .map(|bottom_row| size.height - bottom_row - 1)
+            .unwrap_or(0);
+
+        let tab_width = size.width * TAB_SPACES;
+
+        // Console fonts already include the spacing around each glyph in the bitmap.
+        Ok(Font {
+            name: name.to_string(),
+            glyphs: Arc::new(glyph_table),
+            unknown_glyph,
+            space_width: size.width,
+            size,
+            line_spacing: 0,
+            character_spacing: 0,
+            descent,
+            tab_stops: vec![],
+            tab_width,
+            fallbacks: vec![]
+        })
+    }
+}
+
+fn read_psf1(bytes: &[u8]) -> Result<PsfContents, PsfError> {
+    let mode = *bytes.get(2).ok_or(PsfError::UnexpectedEnd)?;
+    let height = *bytes.get(3).ok_or(PsfError::UnexpectedEnd)? as usize;
+
+    if height == 0 {
+        return Err(PsfError::InvalidHeader("glyph height is zero"));
+    }
+
+    let glyph_count = if mode & PSF1_MODE_512 != 0 { 512 } else { 256 };
+    let size = Size::new(8, height);
+
+    let glyphs_start = 4;
+    let glyphs_end = glyphs_start + glyph_count * height;
+    let glyph_data = bytes.get(glyphs_start..glyphs_end).ok_or(PsfError::UnexpectedEnd)?;
+
+    let glyphs = glyph_data
+        .chunks_exact(height)
+        .map(|rows| glyph_from_rows(rows, &size))
+        .collect();
+
+    let mappings = if mode & (PSF1_MODE_HAS_TABLE | PSF1_MODE_HAS_SEQUENCES) != 0 {
+        Some(read_psf1_unicode_table(&bytes[glyphs_end..], glyph_count)?)
+    } else {
+        None
+    };
+
+    Ok(PsfContents { size, glyphs, mappings })
+}
+
+fn read_psf1_unicode_table(table: &[u8], glyph_count: usize) -> Result<Vec<Vec<char>>, PsfError> {
+    let mut entries = table
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+
+    let mut mappings = vec![];
+
+    for glyph_index in 0..glyph_count {
+        let mut chars = vec![];
+        let mut in_sequence = false;
+
+        loop {
+            let entry = entries.next().ok_or(PsfError::InvalidUnicodeTable(glyph_index))?;
+
+            match entry {
+                PSF1_SEPARATOR => break,
+                PSF1_START_SEQUENCE => in_sequence = true,
+                // Sequences describe combined characters, which a single glyph can't be looked up by.
+                _ if in_sequence => {},
+                code => {
+                    let c = char::from_u32(code as u32).ok_or(PsfError::InvalidUnicodeTable(glyph_index))?;
+                    chars.push(c);
+                }
+            }
+        }
+
+        mappings.push(chars);
+    }
+
+    Ok(mappings)
+}
+
+fn read_psf2(bytes: &[u8]) -> Result<PsfContents, PsfError> {
+    let field = |index: usize| -> Result<usize, PsfError> {
+        let start = 4 + index * 4;
+        let field_bytes = bytes.get(start..start + 4).ok_or(PsfError::UnexpectedEnd)?;
+
+        Ok(u32::from_le_bytes([field_bytes[0], field_bytes[1], field_bytes[2], field_bytes[3]]) as usize)
+    };
+
+    let header_size = field(1)?;
+    if header_size < PSF2_HEADER_SIZE {
+        return Err(PsfError::InvalidHeader("header is smaller than its fields"));
+    }
+
+    let flags = field(2)? as u32;
+    let glyph_count = field(3)?;
+    let bytes_per_glyph = field(4)?;
+    let height = field(5)?;
+    let width = field(6)?;
+
+    if width == 0 || height == 0 {
+        return Err(PsfError::InvalidHeader("glyph size is zero"));
+    }
+
+    if bytes_per_glyph != width.div_ceil(8) * height {
+        return Err(PsfError::InvalidHeader("glyph byte length doesn't match its size"));
+    }
+
+    let size = Size::new(width, height);
+
+    let glyphs_end = glyph_count
+        .checked_mul(bytes_per_glyph)
+        .and_then(|glyphs_size| glyphs_size.checked_add(header_size))
+        .ok_or(PsfError::UnexpectedEnd)?;
+    let glyph_data = bytes.get(header_size..glyphs_end).ok_or(PsfError::UnexpectedEnd)?;
+
+    let glyphs = glyph_data
+        .chunks_exact(bytes_per_glyph)
+        .map(|rows| glyph_from_rows(rows, &size))
+        .collect();
+
+    let mappings = if flags & PSF2_HAS_UNICODE_TABLE != 0 {
+        Some(read_psf2_unicode_table(&bytes[glyphs_end..], glyph_count)?)
+    } else {
+        None
+    };
+
+    Ok(PsfContents { size, glyphs, mappings })
+}
+
+fn read_psf2_unicode_table(table: &[u8], glyph_count: usize) -> Result<Vec<Vec<char>>, PsfError> {
+    let mut entries = table.split(|b| *b == PSF2_SEPARATOR);
+
+    let mut mappings = vec![];
+
+    for glyph_index in 0..glyph_count {
+        let entry = entries.next().ok_or(PsfError::InvalidUnicodeTable(glyph_index))?;
+
+        // Anything after the first sequence marker describes combined characters. Only single characters are kept.
+        let single_chars = entry
+            .split(|b| *b == PSF2_START_SEQUENCE)
+            .next()
+            .unwrap_or_default();
+
+        let chars = std::str::from_utf8(single_chars)
+            .map_err(|_| PsfError::InvalidUnicodeTable(glyph_index))?
+            .chars()
+            .collect();
+
+        mappings.push(chars);
+    }
+
+    Ok(mappings)
+}
+
+impl Glyph {
+    /// The lowest row with any point set.
+    fn bottom_row(&self, size: &Size) -> Option<usize> {
+        (0..size.height)
+            .rev()
+            .find(|y| self.0[y * size.width..(y + 1) * size.width].iter().any(|point| !point.is_empty()))
+    }
+}
+
+/// Unpacks a glyph stored as rows of bits, most significant bit first, each row padded to a whole byte.
+fn glyph_from_rows(rows: &[u8], size: &Size) -> Glyph {
+    let row_length = size.width.div_ceil(8);
+    let mut points = Vec::with_capacity(size.width * size.height);
+
+    for row in rows.chunks_exact(row_length).take(size.height) {
+        for x in 0..size.width {
+            let byte = row[x / 8];
+            let bit = 0x80 >> (x % 8);
+
+            points.push(byte & bit != 0);
+        }
+    }
+
+    Glyph::from(points)
+}
+
+#[test]
+fn load_psf2_with_unicode_table() {
+    let mut bytes = PSF2_MAGIC.to_vec();
+    // version, header size, flags, glyph count, bytes per glyph, height, width
+    for field in [0u32, 32, PSF2_HAS_UNICODE_TABLE, 2, 4, 2, 10] {
+        bytes.extend_from_slice(&field.to_le_bytes());
+    }
+
+    // An empty glyph, and a 10x2 glyph with only its corners set.
+    bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+    bytes.extend_from_slice(&[0x80, 0x40, 0x80, 0x40]);
+
+    bytes.extend_from_slice("? ".as_bytes());
+    bytes.push(PSF2_SEPARATOR);
+    bytes.extend_from_slice("xX".as_bytes());
+    bytes.push(PSF2_START_SEQUENCE);
+    bytes.extend_from_slice("a".as_bytes());
+    bytes.push(PSF2_SEPARATOR);
+
+    let font = Font::from_psf("test", &bytes).unwrap();
+
+    let mut corners = vec![false; 20];
+    corners[0] = true;
+    corners[9] = true;
+    corners[10] = true;
+    corners[19] = true;
+
+    assert_eq!(font.size('x'), &Size::new(10, 2));
+    assert_eq!(font.get_glyph(&'x'), &Glyph::from(corners.clone()));
+    assert_eq!(font.get_glyph(&'X'), &Glyph::from(corners));
+    assert_eq!(font.get_glyph(&'a'), font.default_glyph());
+    assert_eq!(font.default_glyph(), &Glyph::from(vec![false; 20]));
+}
+
+#[test]
+fn rejects_psf2_headers_overlapping_glyphs() {
+    let header = |header_size: u32| {
+        let mut bytes = PSF2_MAGIC.to_vec();
+        for field in [0u32, header_size, 0, 1, 1, 1, 8] {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+        bytes.push(0xFF);
+
+        bytes
+    };
+
+    let error = |bytes: &[u8]| Font::from_psf("test", bytes).err().map(|error| error.to_string());
+
+    assert_eq!(error(&header(16)), Some("InvalidHeader(\"header is smaller than its fields\")".to_string()));
+    assert!(Font::from_psf("test", &header(32)).is_ok());
+}
@@ -0,0 +1,108 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::Font;
+
+/// A collection of fonts looked up by family name and pixel size.
+/// Each family can have a chain of fallback families, used for the glyphs it's missing.
+#[derive(Default, Debug)]
+pub struct FontRegistry {
+    families: HashMap<String, Vec<Arc<Font>>>,
+    fallbacks: HashMap<String, Vec<String>>
+}
+
+impl FontRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with the built-in fonts, under the `"mono"` family.
+    pub fn with_builtin_fonts() -> Self {
+        let mut registry = Self::new();
+
+        registry.register("mono", Font::three_by_three());
+        registry.register("mono", Font::four_by_four());
+        registry.register("mono", Font::four_by_five());
+        registry.register("mono", Font::five_by_seven());
+        registry.register("mono", Font::six_by_eight());
+
+        registry
+    }
+
+    /// Adds a font to a family. Its size is the height of its glyphs,
+    /// replacing any font of the same family that was registered with that size.
+    pub fn register<F: Into<Arc<Font>>>(&mut self, family: &str, font: F) {
+        let font = font.into();
+        let fonts = self.families.entry(family.to_string()).or_default();
+
+        fonts.retain(|registered| registered.glyph_size().height != font.glyph_size().height);
+        fonts.push(font);
+        fonts.sort_by_key(|font| font.glyph_size().height);
+    }
+
+    /// Removes a family and all of its sizes. Fallback chains that mention it are left as they are.
+    pub fn unregister(&mut self, family: &str) {
+        self.families.remove(family);
+    }
+
+    /// Sets the families to look into, in order, when a glyph is missing from `family`.
+    pub fn set_fallbacks(&mut self, family: &str, fallbacks: &[&str]) {
+        let fallbacks = fallbacks.iter().map(|x| x.to_string()).collect();
+
+        self.fallbacks.insert(family.to_string(), fallbacks);
+    }
+
+    pub fn sizes(&self, family: &str) -> Vec<usize> {
+        self.families
+            .get(family)
+            .map(|fonts| fonts.iter().map(|font| font.glyph_size().height).collect())
+            .unwrap_or_default()
+    }
+
+    /// The font of `family` closest to `size`, without its fallback chain.
+    pub fn get(&self, family: &str, size: usize) -> Option<Arc<Font>> {
+        self.families
+            .get(family)?
+            .iter()
+            .min_by_key(|font| font.glyph_size().height.abs_diff(size))
+            .cloned()
+    }
+
+    /// The font of `family` closest to `size`, with the fonts of its fallback families
+    /// (also the closest to `size`) attached.
+    pub fn font(&self, family: &str, size: usize) -> Option<Arc<Font>> {
+        let primary = self.get(family, size)?;
+
+        let Some(fallback_families) = self.fallbacks.get(family) else {
+            return Some(primary);
+        };
+
+        let font = fallback_families
+            .iter()
+            .filter(|fallback| *fallback != family)
+            .filter_map(|fallback| self.get(fallback, size))
+            .fold(primary.as_ref().clone(), |font, fallback| font.with_fallback(fallback));
+
+        Some(Arc::new(font))
+    }
+}
+
+#[test]
+fn falls_back_to_other_family() {
+    let mut registry = FontRegistry::new();
+    registry.register("letters", Font::four_by_four());
+    registry.register("symbols", Font::three_by_three());
+
+    let question_mark = '?';
+
+    let without_fallback = registry.font("letters", 4).unwrap();
+    assert_eq!(without_fallback.get_glyph(&question_mark), without_fallback.default_glyph());
+
+    registry.set_fallbacks("letters", &["symbols"]);
+
+    let with_fallback = registry.font("letters", 4).unwrap();
+    let symbols = Font::three_by_three();
+
+    assert_eq!(with_fallback.get_glyph(&question_mark), symbols.get_glyph(&question_mark));
+    assert_eq!(with_fallback.size(question_mark), symbols.glyph_size());
+    assert_eq!(with_fallback.get_glyph(&'a'), Font::four_by_four().get_glyph(&'a'));
+}
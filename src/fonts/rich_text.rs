@@ -0,0 +1,103 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::layout::geometry::{Rect, Size};
+
+use super::{font_calculations::ResolvedText, style::TextStyle, Font};
+
+/// A text made of spans, each one with its own content, font and style,
+/// laid out and wrapped together as a single paragraph.
+#[derive(Clone, Debug, Default)]
+pub struct RichText<Content> {
+    text: String,
+    spans: Vec<TextSpan<Content>>
+}
+
+#[derive(Clone, Debug)]
+pub struct TextSpan<Content> {
+    /// The bytes of the rich text's string that belong to this span.
+    pub range: Range<usize>,
+    pub font: Arc<Font>,
+    pub content: Content,
+    pub style: TextStyle<Content>
+}
+
+impl<Content: Clone + Default> RichText<Content> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `text` drawn with `font` and `content`.
+    pub fn span<F: Into<Arc<Font>>>(self, text: &str, content: Content, font: F) -> Self {
+        self.styled_span(text, content, font, TextStyle::default())
+    }
+
+    /// Appends `text` drawn with `font` and `content`, decorated by `style`.
+    pub fn styled_span<F: Into<Arc<Font>>>(mut self, text: &str, content: Content, font: F, style: TextStyle<Content>) -> Self {
+        let start = self.text.len();
+        self.text.push_str(text);
+
+        self.spans.push(TextSpan {
+            range: start..self.text.len(),
+            font: font.into(),
+            content,
+            style
+        });
+
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn spans(&self) -> &[TextSpan<Content>] {
+        &self.spans
+    }
+
+    pub(crate) fn spans_mut(&mut self) -> &mut [TextSpan<Content>] {
+        &mut self.spans
+    }
+
+    pub fn calculate_lines(&self, bounds: &Rect) -> ResolvedText<'_, '_> {
+        let fonts: Vec<_> = self.spans
+            .iter()
+            .map(|span| (span.range.clone(), span.font.as_ref()))
+            .collect();
+
+        Font::calculate_span_lines(&self.text, &fonts, bounds)
+    }
+
+    /// The space the styles of all the spans need around the text.
+    pub fn extra_size(&self) -> Size {
+        self.spans
+            .iter()
+            .map(|span| span.style.extra_size(&span.font))
+            .fold(Size::zero(), |total, size| Size::new(total.width.max(size.width), total.height.max(size.height)))
+    }
+}
+
+#[test]
+fn wraps_spans_together() {
+    let text: RichText<u8> = RichText::new()
+        .span("ab ", 1, Font::three_by_three())
+        .span("cd", 2, Font::four_by_five());
+
+    let one_line = text.calculate_lines(&Rect::sized(100, 100));
+    let lines: Vec<_> = one_line.lines().collect();
+
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].bounds().height, 5);
+
+    let spans: Vec<_> = lines[0].glyphs.iter().map(|glyph| glyph.span()).collect();
+    let offsets: Vec<_> = lines[0].glyphs.iter().map(|glyph| glyph.offset().clone()).collect();
+
+    assert_eq!(spans, vec![0, 0, 1, 1]);
+    // The shorter 3x3 glyphs sit on the bottom of the line, and the space takes the width of its own span.
+    assert_eq!(offsets, vec![Size::new(0, 2), Size::new(4, 2), Size::new(12, 0), Size::new(17, 0)]);
+
+    let wrapped = text.calculate_lines(&Rect::sized(15, 100));
+    let heights: Vec<_> = wrapped.lines().map(|line| line.bounds().height).collect();
+
+    assert_eq!(heights, vec![3, 5]);
+    assert_eq!(wrapped.size(), &Size::new(9, 9));
+}
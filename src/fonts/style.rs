@@ -0,0 +1,258 @@
+use crate::layout::geometry::Size;
+
+use super::{Font, Glyph, GlyphPoint};
+
+/// Styling of a text that needs content of its own, so it can't be baked into the font.
+#[derive(Clone, Debug, Default)]
+pub struct TextStyle<Content> {
+    /// Drawn under the text, offset by `TextStyle::SHADOW_OFFSET` down and to the right.
+    pub shadow: Option<Content>,
+    /// Drawn under every line, in the line spacing right below the glyphs,
+    /// or over their bottom row in fonts without line spacing.
+    pub underline: Option<Content>,
+    /// Drawn across the middle of every line, over the glyphs.
+    pub strikethrough: Option<Content>,
+    /// The contents for the `GlyphPoint::Palette` points of multi-color glyphs.
+    pub palette: Vec<Content>
+}
+
+impl<Content> TextStyle<Content> {
+    pub const SHADOW_OFFSET: usize = 1;
+
+    /// The space the style needs around the text drawn with `font`, on top of the size of the glyphs.
+    pub fn extra_size(&self, font: &Font) -> Size {
+        let shadow = if self.shadow.is_some() { Self::SHADOW_OFFSET } else { 0 };
+
+        // The underline of the last line sits below the text.
+        let underline = if self.underline.is_some() {
+            (font.underline_position() + font.decoration_thickness()).saturating_sub(font.glyph_size().height)
+        } else {
+            0
+        };
+
+        Size::new(shadow, shadow + underline)
+    }
+}
+
+// Decoration metrics
+impl Font {
+    /// How thick underlines and strikethroughs are. They fill the space between lines,
+    /// so they grow along with scaled fonts.
+    pub fn decoration_thickness(&self) -> usize {
+        self.line_spacing.max(1)
+    }
+
+    /// Distance from the top of a line to its underline.
+    /// Without space between lines, it takes the bottom row of the glyphs instead of the top row of the next line.
+    pub fn underline_position(&self) -> usize {
+        if self.line_spacing == 0 {
+            self.size.height.saturating_sub(self.decoration_thickness())
+        } else {
+            self.size.height
+        }
+    }
+
+    /// Distance from the top of a line to its strikethrough.
+    pub fn strikethrough_position(&self) -> usize {
+        self.size.height.saturating_sub(self.decoration_thickness()) / 2
+    }
+}
+
+// Synthetic styles, derived from the bitmap of any glyph.
+impl Font {
+    /// A copy of this font where every glyph is smeared one point to the right.
+    pub fn bold(&self) -> Font {
+        self.with_transformed_glyphs(
+            " Bold",
+            &|glyph, size| glyph.emboldened(size),
+            &|size| Size::new(size.width + 1, size.height)
+        )
+    }
+
+    /// A copy of this font where the upper rows of every glyph are shifted to the right.
+    pub fn italic(&self) -> Font {
+        self.with_transformed_glyphs(
+            " Italic",
+            &|glyph, size| glyph.italicized(size),
+            &|size| Size::new(size.width + Glyph::italic_slant(size.height), size.height)
+        )
+    }
+
+    /// A copy of this font where every glyph is replaced by the ring of points around it.
+    pub fn outlined(&self) -> Font {
+        let mut font = self.with_transformed_glyphs(
+            " Outline",
+            &|glyph, size| glyph.outlined(size),
+            &|size| Size::new(size.width + 2, size.height + 2)
+        );
+
+        // The ring grows one row under the baseline.
+        font.apply_to_chain(&|font| font.descent += 1);
+
+        font
+    }
+}
+
+impl Glyph {
+    fn point(&self, size: &Size, x: i64, y: i64) -> GlyphPoint {
+        if x < 0 || y < 0 || x >= size.width as i64 || y >= size.height as i64 {
+            return GlyphPoint::Empty;
+        }
+
+        self.0
+            .get(y as usize * size.width + x as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// One point wider, with every point also filling the one on its right.
+    pub fn emboldened(&self, size: &Size) -> Glyph {
+        let new_width = size.width + 1;
+        let mut points = Vec::with_capacity(new_width * size.height);
+
+        for y in 0..size.height as i64 {
+            for x in 0..new_width as i64 {
+                let point = self.point(size, x, y);
+
+                points.push(if point.is_empty() { self.point(size, x - 1, y) } else { point });
+            }
+        }
+
+        Glyph(points)
+    }
+
+    /// How many points the top row of an italic glyph of `height` is shifted.
+    fn italic_slant(height: usize) -> usize {
+        height.saturating_sub(1) / 2
+    }
+
+    /// Sheared to the right, moving every other row (from the bottom up) one more point.
+    pub fn italicized(&self, size: &Size) -> Glyph {
+        let slant = Self::italic_slant(size.height);
+        let new_width = size.width + slant;
+        let mut points = Vec::with_capacity(new_width * size.height);
+
+        for y in 0..size.height {
+            let shift = (size.height - 1 - y) / 2;
+
+            for x in 0..new_width as i64 {
+                points.push(self.point(size, x - shift as i64, y as i64));
+            }
+        }
+
+        Glyph(points)
+    }
+
+    /// The points around the glyph (including diagonals), one point bigger on every side.
+    /// The outline is drawn with the content of the text, whatever the colors of the glyph were.
+    pub fn outlined(&self, size: &Size) -> Glyph {
+        let new_size = Size::new(size.width + 2, size.height + 2);
+        let mut points = Vec::with_capacity(new_size.width * new_size.height);
+
+        for y in -1..(size.height as i64 + 1) {
+            for x in -1..(size.width as i64 + 1) {
+                if !self.point(size, x, y).is_empty() {
+                    points.push(GlyphPoint::Empty);
+                    continue;
+                }
+
+                let touches_glyph = (-1..=1).any(|dy| {
+                    (-1..=1).any(|dx| !self.point(size, x + dx, y + dy).is_empty())
+                });
+
+                points.push(GlyphPoint::from(touches_glyph));
+            }
+        }
+
+        Glyph(points)
+    }
+}
+
+#[test]
+fn synthetic_glyph_styles() {
+    let (x, o) = (true, false);
+
+    // 1 in the 3x3 font
+    let size = Size::new(3, 3);
+    let glyph = Glyph::from(vec![
+        x, x, o,
+        o, x, o,
+        x, x, x,
+    ]);
+
+    assert_eq!(glyph.emboldened(&size), Glyph::from(vec![
+        x, x, x, o,
+        o, x, x, o,
+        x, x, x, x,
+    ]));
+
+    assert_eq!(glyph.italicized(&size), Glyph::from(vec![
+        o, x, x, o,
+        o, x, o, o,
+        x, x, x, o,
+    ]));
+
+    assert_eq!(glyph.outlined(&size), Glyph::from(vec![
+        x, x, x, x, o,
+        x, o, o, x, o,
+        x, x, o, x, x,
+        x, o, o, o, x,
+        x, x, x, x, x,
+    ]));
+}
+
+#[test]
+fn decorates_each_wrapped_line() {
+    use crate::{
+        layout::{geometry::Rect, node::Node, size_resolution::{SizeCalculator, SizeResolver}},
+        rendering::DrawCommand
+    };
+
+    let node: Node<u8, ()> = Node::text("ab cd", 1).underline(2).strikethrough(3);
+    let bounds = Rect::sized(8, 20);
+
+    let sized_node = SizeCalculator::resolve_size(&node, &bounds, &mut ());
+    let commands = SizeResolver::resolve_draw_commands(&sized_node, &bounds);
+
+    let decorations: Vec<_> = commands
+        .into_iter()
+        .filter_map(|command| match command {
+            DrawCommand::FillRect(rect, content) => Some((rect, content)),
+            _ => None
+        })
+        .collect();
+
+    // 3x3 glyphs with a line spacing of 1: lines start at 0 and 4.
+    assert_eq!(decorations, vec![
+        (Rect::new(0, 3, 7, 1), 2),
+        (Rect::new(0, 1, 7, 1), 3),
+        (Rect::new(0, 7, 7, 1), 2),
+        (Rect::new(0, 5, 7, 1), 3),
+    ]);
+}
+
+#[test]
+fn underlines_lines_without_line_spacing() {
+    use crate::{
+        layout::{geometry::Rect, node::Node, size_resolution::{SizeCalculator, SizeResolver}},
+        rendering::DrawCommand
+    };
+
+    let node: Node<u8, ()> = Node::text_with_font("Ag\nAg", 1, Font::six_by_eight()).underline(2);
+    let bounds = Rect::sized(20, 20);
+
+    let sized_node = SizeCalculator::resolve_size(&node, &bounds, &mut ());
+    let commands = SizeResolver::resolve_draw_commands(&sized_node, &bounds);
+
+    let underlines: Vec<_> = commands
+        .into_iter()
+        .filter_map(|command| match command {
+            DrawCommand::FillRect(rect, _) => Some(rect),
+            _ => None
+        })
+        .collect();
+
+    // 6x8 glyphs without line spacing: lines start at 0 and 8, and are underlined on their bottom rows.
+    assert_eq!(underlines, vec![Rect::new(0, 7, 12, 1), Rect::new(0, 15, 12, 1)]);
+    assert!(underlines[0].max_y() <= 8);
+}
@@ -1,4 +1,4 @@
-use crate::graphics::pixel::{RGBPixel, TwoBitPixel};
+use crate::graphics::pixel::{RGBAPixel, RGBPixel, TwoBitPixel};
 
 use super::Canvas;
 
@@ -26,7 +26,7 @@ impl<'from, FromPixel: Clone + Default, ToPixel: Clone + Default>
 }
 
 impl Canvas<RGBPixel> {
-    pub fn convert_to(&self, threshold: f64) -> CanvasConversion<RGBPixel, TwoBitPixel> {
+    pub fn convert_to(&self, threshold: f64) -> CanvasConversion<'_, RGBPixel, TwoBitPixel> {
         CanvasConversion {
             source_canvas: self,
             conversion: Box::new({
@@ -61,6 +61,44 @@ impl From<Canvas<TwoBitPixel>> for Canvas<RGBPixel> {
     }
 }
 
+impl Canvas<RGBAPixel> {
+    /// The opaque canvas seen when this one is drawn over `background`.
+    pub fn flatten_over(&self, background: RGBPixel) -> CanvasConversion<'_, RGBAPixel, RGBPixel> {
+        CanvasConversion {
+            source_canvas: self,
+            conversion: Box::new(move |pixel| pixel.flatten(&background)),
+        }
+    }
+}
+
+impl From<RGBPixel> for RGBAPixel {
+    fn from(value: RGBPixel) -> Self {
+        RGBAPixel::new(value.r(), value.g(), value.b(), u8::MAX)
+    }
+}
+
+impl From<TwoBitPixel> for RGBAPixel {
+    fn from(value: TwoBitPixel) -> Self {
+        RGBPixel::from(value).into()
+    }
+}
+
+impl From<Canvas<RGBPixel>> for Canvas<RGBAPixel> {
+    fn from(value: Canvas<RGBPixel>) -> Self {
+        let new_pixels = value.contents.into_iter().map(|x| x.into()).collect();
+
+        Canvas::create_with_content(new_pixels, &value.size)
+    }
+}
+
+impl From<Canvas<TwoBitPixel>> for Canvas<RGBAPixel> {
+    fn from(value: Canvas<TwoBitPixel>) -> Self {
+        let new_pixels = value.contents.iter().map(|x| (*x).into()).collect();
+
+        Canvas::create_with_content(new_pixels, &value.size)
+    }
+}
+
 #[test]
 fn convert_rgb_canvas_to_twobit() {
     let mut rgb_canvas: Canvas<RGBPixel> = Canvas::create(5, 5);
@@ -73,3 +111,27 @@ fn convert_rgb_canvas_to_twobit() {
 
     assert_eq!(rgb_canvas.contents, new_rgb_canvas.contents);
 }
+
+#[test]
+fn blends_rgba_canvas() {
+    use crate::{graphics::pixel::Blend, layout::geometry::Rect, rendering::canvas::Drawable};
+
+    let mut canvas: Canvas<RGBAPixel> = Canvas::create(2, 1);
+    canvas.clear_with(&RGBPixel::new(0, 0, 0));
+
+    canvas.fill_rect(&Rect::sized(1, 1), &RGBAPixel::new(255, 255, 255, 128));
+    canvas.fill_rect(&Rect::new(1, 0, 1, 1), &RGBAPixel::transparent());
+
+    // Half white over opaque black, and nothing over opaque black.
+    assert_eq!(canvas.contents, vec![RGBAPixel::new(128, 128, 128, 255), RGBAPixel::new(0, 0, 0, 255)]);
+
+    // Half red over transparent stays half red.
+    let over_nothing = RGBAPixel::new(255, 0, 0, 128).over(&RGBAPixel::transparent());
+    assert_eq!(over_nothing, RGBAPixel::new(255, 0, 0, 128));
+
+    let mut overlay: Canvas<RGBAPixel> = Canvas::create(1, 1);
+    overlay.fill_rect(&Rect::sized(1, 1), &RGBAPixel::new(0, 0, 0, 51));
+
+    let flattened: Canvas<RGBPixel> = overlay.flatten_over(RGBPixel::new(255, 255, 255)).into();
+    assert_eq!(flattened.contents, vec![RGBPixel::new(204, 204, 204)]);
+}
@@ -2,9 +2,12 @@ pub mod conversions;
 
 use crate::layout::geometry::{Rect, Size};
 
+#[derive(Debug)]
 pub struct Canvas<Content: Default + Clone> {
     pub(crate) size: Size,
-    pub(crate) contents: Vec<Content>
+    pub(crate) contents: Vec<Content>,
+    /// Whether the edges of shapes are blended with what's below them.
+    pub(crate) anti_aliasing: bool
 }
 
 impl<Content: Default + Clone> Default for Canvas<Content> {
@@ -17,12 +20,13 @@ impl<Content: Default + Clone> Canvas<Content> {
     pub fn new() -> Self {
         Canvas {
             size: Size::zero(),
-            contents: Vec::new()
+            contents: Vec::new(),
+            anti_aliasing: false
         }
     }
 
     pub fn create_with_content(contents: Vec<Content>, size: &Size) -> Self {
-        Canvas { size: size.clone(), contents }
+        Canvas { size: size.clone(), contents, anti_aliasing: false }
     }
 
     pub fn create_in_bounds(size: &Size) -> Self {
@@ -31,7 +35,8 @@ impl<Content: Default + Clone> Canvas<Content> {
             contents: vec![
                 Default::default();
                 size.width * size.height
-            ]
+            ],
+            anti_aliasing: false
         }
     }
 
@@ -41,10 +46,18 @@ impl<Content: Default + Clone> Canvas<Content> {
             contents: vec![
                 Default::default();
                 width * height
-            ]
+            ],
+            anti_aliasing: false
         }
     }
 
+    /// Draws shapes with smooth edges, for pixels that can be mixed together like `RGBPixel`.
+    pub fn with_anti_aliasing(mut self, anti_aliasing: bool) -> Self {
+        self.anti_aliasing = anti_aliasing;
+
+        self
+    }
+
     pub fn bounds(&self) -> Rect {
         Rect::from_size(&self.size)
     }
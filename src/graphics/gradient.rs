@@ -0,0 +1,138 @@
+use crate::layout::geometry::Size;
+
+use super::pixel::Blend;
+
+/// A fill that goes through the contents of its stops, evaluated for each pixel of the bounds it's drawn in.
+/// Points are in unit coordinates of those bounds: `(0.0, 0.0)` is the top left corner and `(1.0, 1.0)` the bottom right.
+#[derive(Clone, Debug)]
+pub struct Gradient<Content> {
+    kind: GradientKind,
+    /// Positions along the gradient, from 0 to 1, and the content there. Kept in order.
+    stops: Vec<(f32, Content)>
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GradientKind {
+    /// Goes from the first stop at `start` to the last one at `end`, the same across the line between them.
+    Linear { start: (f32, f32), end: (f32, f32) },
+    /// Goes from the first stop at `center` to the last one `radius` away, stretched with the bounds.
+    Radial { center: (f32, f32), radius: f32 }
+}
+
+impl<Content: Clone> Gradient<Content> {
+    pub fn linear(start: (f32, f32), end: (f32, f32)) -> Self {
+        Gradient { kind: GradientKind::Linear { start, end }, stops: vec![] }
+    }
+
+    /// From the left edge to the right edge.
+    pub fn horizontal() -> Self {
+        Self::linear((0.0, 0.5), (1.0, 0.5))
+    }
+
+    /// From the top edge to the bottom edge.
+    pub fn vertical() -> Self {
+        Self::linear((0.5, 0.0), (0.5, 1.0))
+    }
+
+    pub fn radial(center: (f32, f32), radius: f32) -> Self {
+        Gradient { kind: GradientKind::Radial { center, radius }, stops: vec![] }
+    }
+
+    /// Adds `content` at `position` (from 0 to 1) along the gradient.
+    pub fn stop(mut self, position: f32, content: Content) -> Self {
+        let position = position.clamp(0.0, 1.0);
+        let index = self.stops.partition_point(|(stop, _)| *stop <= position);
+
+        self.stops.insert(index, (position, content));
+
+        self
+    }
+
+    pub fn kind(&self) -> &GradientKind {
+        &self.kind
+    }
+
+    pub fn stops(&self) -> &[(f32, Content)] {
+        &self.stops
+    }
+
+    /// How far along the gradient the center of the pixel at `x`, `y` is, from 0 to 1.
+    pub fn position_at(&self, x: usize, y: usize, size: &Size) -> f32 {
+        let point = (
+            (x as f32 + 0.5) / size.width.max(1) as f32,
+            (y as f32 + 0.5) / size.height.max(1) as f32
+        );
+
+        let position = match self.kind {
+            GradientKind::Linear { start, end } => {
+                let direction = (end.0 - start.0, end.1 - start.1);
+                let length = direction.0 * direction.0 + direction.1 * direction.1;
+
+                if length == 0.0 {
+                    return 0.0;
+                }
+
+                ((point.0 - start.0) * direction.0 + (point.1 - start.1) * direction.1) / length
+            }
+            GradientKind::Radial { center, radius } => {
+                if radius <= 0.0 {
+                    return 1.0;
+                }
+
+                (point.0 - center.0).hypot(point.1 - center.1) / radius
+            }
+        };
+
+        position.clamp(0.0, 1.0)
+    }
+}
+
+impl<Content: Blend> Gradient<Content> {
+    /// The content of the pixel at `x`, `y` of bounds as big as `size`,
+    /// mixed from the stops around it. A gradient without stops has no content.
+    pub fn content_at(&self, x: usize, y: usize, size: &Size) -> Option<Content> {
+        let position = self.position_at(x, y, size);
+
+        let next = self.stops.partition_point(|(stop, _)| *stop < position);
+
+        let (start, start_content) = self.stops.get(next.saturating_sub(1))?;
+        let Some((end, end_content)) = self.stops.get(next) else {
+            return Some(start_content.clone());
+        };
+
+        if end <= start {
+            return Some(end_content.clone());
+        }
+
+        let coverage = ((position - start) / (end - start) * 255.0).round() as u8;
+
+        Some(end_content.partially_over(start_content, coverage))
+    }
+}
+
+#[test]
+fn mixes_stops() {
+    use super::pixel::RGBPixel;
+
+    let gradient = Gradient::horizontal()
+        .stop(1.0, RGBPixel::new(255, 0, 0))
+        .stop(0.0, RGBPixel::new(0, 0, 0))
+        .stop(0.5, RGBPixel::new(0, 0, 255));
+
+    let size = Size::new(4, 1);
+    let pixels: Vec<_> = (0..4).map(|x| gradient.content_at(x, 0, &size).unwrap()).collect();
+
+    assert_eq!(pixels, vec![
+        RGBPixel::new(0, 0, 64),
+        RGBPixel::new(0, 0, 191),
+        RGBPixel::new(64, 0, 191),
+        RGBPixel::new(191, 0, 64),
+    ]);
+
+    let radial = Gradient::radial((0.5, 0.5), 0.5).stop(0.0, RGBPixel::new(255, 255, 255)).stop(1.0, RGBPixel::new(0, 0, 0));
+    let size = Size::new(3, 3);
+
+    assert_eq!(radial.content_at(1, 1, &size), Some(RGBPixel::new(255, 255, 255)));
+    assert_eq!(radial.content_at(0, 1, &size), Some(RGBPixel::new(85, 85, 85)));
+    assert_eq!(radial.content_at(0, 0, &size), Some(RGBPixel::new(15, 15, 15)));
+}
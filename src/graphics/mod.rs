@@ -1,2 +1,3 @@
 pub mod canvas;
+pub mod gradient;
 pub mod pixel;
\ No newline at end of file
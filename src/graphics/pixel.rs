@@ -1,6 +1,27 @@
+/// How a pixel is combined with the one already on a canvas when it's drawn on top of it.
+/// Opaque pixels replace what's below them, which is what an empty implementation does.
+pub trait Blend: Clone {
+    fn over(&self, _below: &Self) -> Self {
+        self.clone()
+    }
+
+    /// Draws the pixel over `below` when it only covers `coverage` (out of 255) of it,
+    /// like on the edges of anti-aliased shapes. Pixels that can't be mixed are drawn
+    /// when they cover at least half of the one below.
+    fn partially_over(&self, below: &Self, coverage: u8) -> Self {
+        if coverage >= 128 {
+            self.over(below)
+        } else {
+            below.clone()
+        }
+    }
+}
+
 #[derive(Default, Clone, Copy, Debug, PartialEq)]
 pub struct TwoBitPixel(pub bool);
 
+impl Blend for TwoBitPixel {}
+
 impl From<bool> for TwoBitPixel {
     fn from(value: bool) -> Self {
         Self(value)
@@ -89,3 +110,110 @@ impl Default for RGBPixel {
         Self::black()
     }
 }
+
+impl Blend for RGBPixel {
+    fn partially_over(&self, below: &Self, coverage: u8) -> Self {
+        let coverage = coverage as u32;
+        let mix = |top: u8, below: u8| -> u8 {
+            ((top as u32 * coverage + below as u32 * (255 - coverage) + 127) / 255) as u8
+        };
+
+        RGBPixel {
+            r: mix(self.r, below.r),
+            g: mix(self.g, below.g),
+            b: mix(self.b, below.b),
+        }
+    }
+}
+
+/// A color with an alpha channel, from 0 (transparent) to 255 (opaque).
+/// The color channels are not premultiplied by the alpha.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RGBAPixel {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+}
+
+impl RGBAPixel {
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        RGBAPixel { r, g, b, a }
+    }
+
+    pub fn transparent() -> Self {
+        RGBAPixel::new(0, 0, 0, 0)
+    }
+
+    /// The same color with a different alpha.
+    pub fn with_alpha(&self, a: u8) -> Self {
+        RGBAPixel { a, ..self.clone() }
+    }
+}
+
+impl RGBAPixel {
+    pub fn r(&self) -> u8 {
+        self.r
+    }
+
+    pub fn g(&self) -> u8 {
+        self.g
+    }
+
+    pub fn b(&self) -> u8 {
+        self.b
+    }
+
+    pub fn a(&self) -> u8 {
+        self.a
+    }
+}
+
+impl RGBAPixel {
+    /// The opaque color seen when this pixel is drawn over `background`.
+    pub fn flatten(&self, background: &RGBPixel) -> RGBPixel {
+        let flattened = self.over(&background.clone().into());
+
+        RGBPixel::new(flattened.r, flattened.g, flattened.b)
+    }
+}
+
+impl Default for RGBAPixel {
+    fn default() -> Self {
+        Self::transparent()
+    }
+}
+
+impl Blend for RGBAPixel {
+    /// Source-over compositing.
+    fn over(&self, below: &Self) -> Self {
+        let (top_alpha, below_alpha) = (self.a as u32, below.a as u32);
+
+        // The part of the pixel below that shows through, out of 255 * 255.
+        let below_weight = below_alpha * (255 - top_alpha);
+        let alpha = top_alpha * 255 + below_weight;
+
+        if alpha == 0 {
+            return Self::transparent();
+        }
+
+        let channel = |top: u8, below: u8| -> u8 {
+            let value = top as u32 * top_alpha * 255 + below as u32 * below_weight;
+
+            ((value + alpha / 2) / alpha) as u8
+        };
+
+        RGBAPixel {
+            r: channel(self.r, below.r),
+            g: channel(self.g, below.g),
+            b: channel(self.b, below.b),
+            a: ((alpha + 127) / 255) as u8,
+        }
+    }
+
+    fn partially_over(&self, below: &Self, coverage: u8) -> Self {
+        let alpha = (self.a as u32 * coverage as u32 + 127) / 255;
+
+        self.with_alpha(alpha as u8).over(below)
+    }
+}
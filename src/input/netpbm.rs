@@ -0,0 +1,334 @@
+use std::{error::Error, fmt::Display, path::Path};
+
+use crate::{
+    graphics::{
+        canvas::Canvas,
+        pixel::{RGBPixel, TwoBitPixel},
+    },
+    layout::geometry::Size,
+};
+
+#[derive(Debug)]
+pub enum NetpbmError {
+    Io(std::io::Error),
+    UnknownFormat,
+    UnexpectedEnd,
+    InvalidHeader(&'static str),
+    /// A sample that isn't a number, or is bigger than the maximum value. The index counts every sample,
+    /// three per pixel in color images.
+    InvalidSample(usize)
+}
+
+impl Error for NetpbmError {}
+
+impl Display for NetpbmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self))
+    }
+}
+
+impl From<std::io::Error> for NetpbmError {
+    fn from(value: std::io::Error) -> Self {
+        NetpbmError::Io(value)
+    }
+}
+
+/// The samples read from a netpbm file, before turning them into pixels.
+enum Raster {
+    /// PBM (P1, P4), where set bits are black.
+    Bits(Vec<bool>),
+    /// PGM (P2, P5), from black to the maximum value.
+    Gray(Vec<u16>, u16),
+    /// PPM (P3, P6), three samples per pixel.
+    Color(Vec<[u16; 3]>, u16)
+}
+
+impl Canvas<RGBPixel> {
+    /// Reads any of the netpbm formats, from P1 to P6, from disk.
+    pub fn open_netpbm<P: AsRef<Path>>(path: P) -> Result<Self, NetpbmError> {
+        let bytes = std::fs::read(path)?;
+
+        Self::from_netpbm(&bytes)
+    }
+
+    /// Reads an image in any of the netpbm formats. Bitmaps are drawn with the white and black of the palette,
+    /// like `TwoBitPixel`s, and gray levels are scaled to the full range of each color.
+    pub fn from_netpbm(bytes: &[u8]) -> Result<Self, NetpbmError> {
+        let (size, raster) = read_netpbm(bytes)?;
+
+        let pixels = match raster {
+            Raster::Bits(bits) => bits.into_iter().map(|black| TwoBitPixel(!black).into()).collect(),
+            Raster::Gray(values, max) => values
+                .into_iter()
+                .map(|value| {
+                    let level = scale(value, max);
+
+                    RGBPixel::new(level, level, level)
+                })
+                .collect(),
+            Raster::Color(colors, max) => colors.into_iter().map(|color| color_pixel(color, max)).collect()
+        };
+
+        Ok(Canvas::create_with_content(pixels, &size))
+    }
+}
+
+impl Canvas<TwoBitPixel> {
+    /// Reads any of the netpbm formats, from P1 to P6, from disk.
+    pub fn open_netpbm<P: AsRef<Path>>(path: P) -> Result<Self, NetpbmError> {
+        let bytes = std::fs::read(path)?;
+
+        Self::from_netpbm(&bytes)
+    }
+
+    /// Reads an image in any of the netpbm formats. Gray levels over half of the maximum value are set,
+    /// and colors are converted by their brightness, like `Canvas::convert_to` with a threshold of 0.5.
+    pub fn from_netpbm(bytes: &[u8]) -> Result<Self, NetpbmError> {
+        let (size, raster) = read_netpbm(bytes)?;
+
+        let pixels = match raster {
+            Raster::Bits(bits) => bits.into_iter().map(|black| TwoBitPixel(!black)).collect(),
+            Raster::Gray(values, max) => values.into_iter().map(|value| TwoBitPixel(value as u32 * 2 > max as u32)).collect(),
+            Raster::Color(colors, max) => colors
+                .into_iter()
+                .map(|color| TwoBitPixel(color_pixel(color, max).brightness() > 0.5))
+                .collect()
+        };
+
+        Ok(Canvas::create_with_content(pixels, &size))
+    }
+}
+
+fn read_netpbm(bytes: &[u8]) -> Result<(Size, Raster), NetpbmError> {
+    let mut reader = Reader { bytes, position: 0 };
+
+    let format = match bytes.get(..2) {
+        Some([b'P', format @ b'1'..=b'6']) => *format,
+        Some(_) => return Err(NetpbmError::UnknownFormat),
+        None => return Err(NetpbmError::UnexpectedEnd)
+    };
+    reader.position = 2;
+
+    let width = reader.header_number("width")?;
+    let height = reader.header_number("height")?;
+    let count = width.checked_mul(height).ok_or(NetpbmError::InvalidHeader("size"))?;
+
+    let max = match format {
+        b'1' | b'4' => 1,
+        _ => {
+            let max = reader.header_number("max value")?;
+
+            u16::try_from(max).ok().filter(|max| *max > 0).ok_or(NetpbmError::InvalidHeader("max value"))?
+        }
+    };
+
+    let size = Size::new(width, height);
+    let color_samples = || count.checked_mul(3).ok_or(NetpbmError::InvalidHeader("size"));
+
+    let raster = match format {
+        b'1' => Raster::Bits((0..count).map(|index| reader.ascii_bit(index)).collect::<Result<_, _>>()?),
+        b'2' => Raster::Gray(reader.ascii_samples(count, max)?, max),
+        b'3' => Raster::Color(colors(reader.ascii_samples(color_samples()?, max)?), max),
+        b'4' => {
+            reader.raster_start()?;
+            let row_bytes = width.div_ceil(8);
+            let rows = reader.take(row_bytes * height)?;
+
+            let bits = (0..count)
+                .map(|index| {
+                    let (x, y) = (index % width, index / width);
+
+                    rows[y * row_bytes + x / 8] & (0x80 >> (x % 8)) != 0
+                })
+                .collect();
+
+            Raster::Bits(bits)
+        }
+        b'5' => {
+            reader.raster_start()?;
+            Raster::Gray(reader.binary_samples(count, max)?, max)
+        }
+        _ => {
+            reader.raster_start()?;
+            Raster::Color(colors(reader.binary_samples(color_samples()?, max)?), max)
+        }
+    };
+
+    Ok((size, raster))
+}
+
+/// `value` out of `max`, scaled to a byte.
+fn scale(value: u16, max: u16) -> u8 {
+    (value as u32 * u8::MAX as u32 / max as u32) as u8
+}
+
+fn color_pixel([r, g, b]: [u16; 3], max: u16) -> RGBPixel {
+    RGBPixel::new(scale(r, max), scale(g, max), scale(b, max))
+}
+
+fn colors(samples: Vec<u16>) -> Vec<[u16; 3]> {
+    samples.chunks_exact(3).map(|rgb| [rgb[0], rgb[1], rgb[2]]).collect()
+}
+
+struct Reader<'bytes> {
+    bytes: &'bytes [u8],
+    position: usize
+}
+
+impl<'bytes> Reader<'bytes> {
+    /// Skips whitespace and comments, which go from `#` to the end of the line.
+    fn skip_separators(&mut self) {
+        while let Some(byte) = self.bytes.get(self.position) {
+            match byte {
+                b'#' => {
+                    while !matches!(self.bytes.get(self.position), Some(b'\n' | b'\r') | None) {
+                        self.position += 1;
+                    }
+                }
+                byte if byte.is_ascii_whitespace() => self.position += 1,
+                _ => return
+            }
+        }
+    }
+
+    fn token(&mut self) -> Option<&'bytes [u8]> {
+        self.skip_separators();
+
+        let start = self.position;
+        while self.bytes.get(self.position).is_some_and(|byte| !byte.is_ascii_whitespace() && *byte != b'#') {
+            self.position += 1;
+        }
+
+        (self.position > start).then(|| &self.bytes[start..self.position])
+    }
+
+    fn header_number(&mut self, name: &'static str) -> Result<usize, NetpbmError> {
+        let token = self.token().ok_or(NetpbmError::UnexpectedEnd)?;
+
+        std::str::from_utf8(token)
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or(NetpbmError::InvalidHeader(name))
+    }
+
+    /// PBM bits don't need anything between them.
+    fn ascii_bit(&mut self, index: usize) -> Result<bool, NetpbmError> {
+        self.skip_separators();
+
+        let bit = match self.bytes.get(self.position) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            Some(_) => return Err(NetpbmError::InvalidSample(index)),
+            None => return Err(NetpbmError::UnexpectedEnd)
+        };
+        self.position += 1;
+
+        Ok(bit)
+    }
+
+    fn ascii_samples(&mut self, count: usize, max: u16) -> Result<Vec<u16>, NetpbmError> {
+        (0..count)
+            .map(|index| {
+                let token = self.token().ok_or(NetpbmError::UnexpectedEnd)?;
+
+                std::str::from_utf8(token)
+                    .ok()
+                    .and_then(|sample| sample.parse().ok())
+                    .filter(|sample| *sample <= max)
+                    .ok_or(NetpbmError::InvalidSample(index))
+            })
+            .collect()
+    }
+
+    /// Binary rasters start after the single whitespace byte that ends the header.
+    fn raster_start(&mut self) -> Result<(), NetpbmError> {
+        match self.bytes.get(self.position) {
+            Some(byte) if byte.is_ascii_whitespace() => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(_) => Err(NetpbmError::InvalidHeader("raster start")),
+            None => Err(NetpbmError::UnexpectedEnd)
+        }
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'bytes [u8], NetpbmError> {
+        let end = self.position.checked_add(count).ok_or(NetpbmError::UnexpectedEnd)?;
+        let bytes = self.bytes.get(self.position..end).ok_or(NetpbmError::UnexpectedEnd)?;
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    /// Samples take two bytes, most significant first, when the maximum value doesn't fit in one.
+    fn binary_samples(&mut self, count: usize, max: u16) -> Result<Vec<u16>, NetpbmError> {
+        let sample_bytes = if max > u8::MAX as u16 { 2 } else { 1 };
+        let bytes = self.take(count.checked_mul(sample_bytes).ok_or(NetpbmError::UnexpectedEnd)?)?;
+
+        bytes
+            .chunks_exact(sample_bytes)
+            .enumerate()
+            .map(|(index, sample)| {
+                let value = sample.iter().fold(0u16, |value, byte| (value << 8) | *byte as u16);
+
+                if value > max { Err(NetpbmError::InvalidSample(index)) } else { Ok(value) }
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn reads_every_format() {
+    use crate::output::ppm_output::PpmOutput;
+
+    let (x, o) = (TwoBitPixel(true), TwoBitPixel(false));
+    let expected = vec![o, x, x, o];
+
+    let two_bit = |bytes: &[u8]| Canvas::<TwoBitPixel>::from_netpbm(bytes).map(|canvas| canvas.contents);
+
+    assert_eq!(two_bit(b"P1\n# comment\n2 2\n1001").unwrap(), expected);
+    assert_eq!(two_bit(b"P4 2 2\n\x80\x40").unwrap(), expected);
+    assert_eq!(two_bit(b"P2 2 2 255\n0 255 200 100").unwrap(), expected);
+    assert_eq!(two_bit(b"P5 2 2 255\n\x00\xff\xc8\x64").unwrap(), expected);
+    assert_eq!(two_bit(b"P6 2 2 65535\n\0\0\0\0\0\0\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\0\0\0\0\0\0").unwrap(), expected);
+
+    let rgb = Canvas::<RGBPixel>::from_netpbm(b"P3 2 1 15 15 0 0 0 0 15").unwrap();
+    assert_eq!(rgb.contents, vec![RGBPixel::new(255, 0, 0), RGBPixel::new(0, 0, 255)]);
+
+    // What the outputs write can be read back.
+    let written = Canvas::create_with_content(vec![RGBPixel::red(), RGBPixel::blue()], &Size::new(1, 2));
+    let read = Canvas::<RGBPixel>::from_netpbm(written.output().as_bytes()).unwrap();
+    assert_eq!(read.contents, written.contents);
+
+    let snapshot = include_bytes!("../../test_outputs/horizontal_stack.ppm");
+    let canvas = Canvas::<TwoBitPixel>::from_netpbm(snapshot).unwrap();
+    assert_eq!(canvas.size, Size::new(100, 100));
+    assert_eq!(canvas.output().as_bytes(), snapshot);
+}
+
+#[test]
+fn rejects_malformed_files() {
+    let read = |bytes: &[u8]| Canvas::<RGBPixel>::from_netpbm(bytes).err().map(|error| error.to_string());
+
+    assert_eq!(read(b"P7 1 1"), Some("UnknownFormat".to_string()));
+    assert_eq!(read(b"P3 1"), Some("UnexpectedEnd".to_string()));
+    assert_eq!(read(b"P3 one 1 255"), Some("InvalidHeader(\"width\")".to_string()));
+    assert_eq!(read(b"P2 1 1 0\n0"), Some("InvalidHeader(\"max value\")".to_string()));
+    assert_eq!(read(b"P2 2 1 1\n0 2"), Some("InvalidSample(1)".to_string()));
+    assert_eq!(read(b"P5 2 1 255\n\x00"), Some("UnexpectedEnd".to_string()));
+}
+
+#[test]
+fn rejects_oversized_headers() {
+    let read = |bytes: &[u8]| Canvas::<RGBPixel>::from_netpbm(bytes).err().map(|error| error.to_string());
+    let size_error = Some("InvalidHeader(\"size\")".to_string());
+
+    // Too many pixels, and too many samples for their colors.
+    assert_eq!(read(b"P2 4294967296 4294967296 255\n"), size_error);
+    assert_eq!(read(b"P3 9223372036854775808 1 255\n"), size_error);
+    assert_eq!(read(b"P6 9223372036854775808 1 255\n"), size_error);
+    assert_eq!(
+        Canvas::<TwoBitPixel>::from_netpbm(b"P6 9223372036854775808 1 255\n").err().map(|error| error.to_string()),
+        size_error
+    );
+}
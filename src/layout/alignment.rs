@@ -11,7 +11,13 @@ pub enum HorizontalAlignment {
 pub enum VerticalAlignment {
     Top,
     Center,
-    Bottom
+    Bottom,
+    /// Lines up the baselines of the first line of text in each item.
+    /// Items without text use their bottom edge as a baseline.
+    FirstBaseline,
+    /// Lines up the baselines of the last line of text in each item.
+    /// Items without text use their bottom edge as a baseline.
+    LastBaseline
 }
 
 #[derive(Clone, Debug)]
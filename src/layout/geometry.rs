@@ -1,6 +1,8 @@
-use core::f64;
+use core::{f32, f64};
 
-#[derive(Clone, Debug)]
+use super::path::Path;
+
+#[derive(Clone, PartialEq, Debug)]
 pub struct Rect {
     pub x: i64,
     pub y: i64,
@@ -174,7 +176,7 @@ pub struct Matrix<Item: Clone> {
 
 impl<Item: Clone> Matrix<Item> {
     pub fn with_rows(data: &[Item], row_count: usize) -> Self {
-        assert!(data.len() % row_count == 0, "Matrix must completely fill the grid");
+        assert!(data.len().is_multiple_of(row_count), "Matrix must completely fill the grid");
 
         let col_count = data.len() / row_count;
         Matrix { shape: (col_count, row_count), data: data.iter().map(|x| (*x).clone()).collect() }
@@ -229,7 +231,22 @@ pub enum Shape {
     Rectangle,
     RoundedRectangle(CornerRadius),
     Ellipse,
-    Capsule
+    Capsule,
+    /// Any outline, placed in the bounds of the shape. Strokes follow it even where it isn't closed.
+    Path(Path),
+    /// A regular polygon with this many sides, with its corners on the ellipse of the bounds and the first one at the top.
+    Polygon(usize),
+    /// A star with this many points on the ellipse of the bounds, the first one at the top.
+    /// The corners between them are as far from the center as the ratio, from 0 to 1.
+    Star(usize, f32),
+    /// The part of the ellipse of the bounds from the start to the end angle, in degrees clockwise from the right.
+    /// Filling it fills between the arc and the line across its ends.
+    Arc(f32, f32),
+    /// The slice of the ellipse of the bounds from the start to the end angle, in degrees clockwise from the right.
+    Pie(f32, f32),
+    /// A line between two points, in unit coordinates of the bounds. There's nothing to fill,
+    /// so filling it draws it one pixel thick.
+    Line((f32, f32), (f32, f32))
 }
 
 impl Shape {
@@ -240,4 +257,42 @@ impl Shape {
     pub fn rounded_rect_with_corners(top_left: usize, top_right: usize, bottom_left: usize, bottom_right: usize) -> Shape {
         Shape::RoundedRectangle(CornerRadius::new(top_left, top_right, bottom_left, bottom_right))
     }
+
+    /// The outline of the shapes that are drawn from a path, in unit coordinates unless it's a pixel path.
+    /// Shapes that can't be drawn, like a polygon with less than 3 sides, have none.
+    pub fn path(&self) -> Option<Path> {
+        let center = (0.5, 0.5);
+        let radius = (0.5, 0.5);
+
+        match *self {
+            Shape::Rectangle | Shape::RoundedRectangle(_) | Shape::Ellipse | Shape::Capsule => None,
+            Shape::Path(ref path) => Some(path.clone()),
+            Shape::Polygon(sides) if sides >= 3 => Some(Self::around_center((0..sides).map(|_| 1.0))),
+            Shape::Star(points, inner_ratio) if points >= 2 => {
+                let inner_ratio = inner_ratio.clamp(0.0, 1.0);
+
+                Some(Self::around_center((0..points * 2).map(|corner| if corner % 2 == 0 { 1.0 } else { inner_ratio })))
+            }
+            Shape::Arc(start, end) => Some(Path::unit().arc(center, radius, start, end - start)),
+            Shape::Pie(start, end) => Some(Path::unit().move_to(center.0, center.1).arc(center, radius, start, end - start).close()),
+            Shape::Line(start, end) => Some(Path::unit().move_to(start.0, start.1).line_to(end.0, end.1)),
+            Shape::Polygon(_) | Shape::Star(..) => None
+        }
+    }
+
+    /// A closed path through corners evenly spread around the center, starting at the top.
+    /// Each corner is as far from the center as its ratio of the ellipse of the bounds.
+    fn around_center<Ratios: ExactSizeIterator<Item = f32>>(ratios: Ratios) -> Path {
+        let step = f32::consts::TAU / ratios.len() as f32;
+        let mut path = Path::unit();
+
+        for (corner, ratio) in ratios.enumerate() {
+            let angle = step * corner as f32 - f32::consts::FRAC_PI_2;
+            let (x, y) = (0.5 + 0.5 * ratio * angle.cos(), 0.5 + 0.5 * ratio * angle.sin());
+
+            path = if corner == 0 { path.move_to(x, y) } else { path.line_to(x, y) };
+        }
+
+        path.close()
+    }
 }
\ No newline at end of file
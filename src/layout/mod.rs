@@ -1,4 +1,5 @@
 pub mod geometry;
+pub mod path;
 pub mod alignment;
 pub mod sizing;
 pub mod node;
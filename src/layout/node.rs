@@ -1,5 +1,12 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use crate::fonts::Font;
+use crate::fonts::icons::{Icon, IconSize};
+use crate::fonts::rich_text::RichText;
+use crate::fonts::style::TextStyle;
+use crate::graphics::canvas::Canvas;
+use crate::graphics::gradient::Gradient;
+use crate::rendering::effects::Effect;
 use crate::layout::alignment::Edge;
 
 use super::alignment;
@@ -12,6 +19,19 @@ pub enum ShapeBehavior {
     Fill
 }
 
+/// How an image is drawn in a frame of a different size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ContentMode {
+    /// Scaled to be as big as it can inside the frame, keeping its aspect ratio.
+    Fit,
+    /// Scaled to cover the whole frame, keeping its aspect ratio. The parts outside of the frame are cut.
+    Fill,
+    /// Scaled to the size of the frame.
+    Stretch,
+    /// Not scaled, in the center of the frame.
+    Center
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum DetachedBehavior {
     Overlay,
@@ -20,7 +40,8 @@ pub enum DetachedBehavior {
 
 #[derive(Clone, Debug)]
 pub enum Node<Content: Clone + Default + std::fmt::Debug, Ctx: Clone + std::fmt::Debug> {
-    Text(String, &'static Font, Content),
+    Text(String, Arc<Font>, Content, TextStyle<Content>),
+    RichText(RichText<Content>),
     Width(usize, Box<Node<Content, Ctx>>),
     Height(usize, Box<Node<Content, Ctx>>),
     TopPadding(usize, Box<Node<Content, Ctx>>),
@@ -34,6 +55,8 @@ pub enum Node<Content: Clone + Default + std::fmt::Debug, Ctx: Clone + std::fmt:
     VTopAlign(Box<Node<Content, Ctx>>),
     HLeftAlign(Box<Node<Content, Ctx>>),
     Background(Content, Box<Node<Content, Ctx>>),
+    GradientBackground(Gradient<Content>, Box<Node<Content, Ctx>>),
+    Effect(Effect<Content>, Box<Node<Content, Ctx>>),
     Detached(Box<Node<Content, Ctx>>, alignment::Alignment, DetachedBehavior, Box<Node<Content, Ctx>>),
     TopBorder(usize, Content, Box<Node<Content, Ctx>>),
     BottomBorder(usize, Content, Box<Node<Content, Ctx>>),
@@ -47,20 +70,72 @@ pub enum Node<Content: Clone + Default + std::fmt::Debug, Ctx: Clone + std::fmt:
     // DrawCanvas(fn(&mut Ctx, &Rect)->crate::canvas::TextCanvas),
     WithContext(fn(&Ctx)->Node<Content, Ctx>),
     
-    Shape(Shape, ShapeBehavior, Content)
+    Shape(Shape, ShapeBehavior, Content),
+    /// A shape filled with a gradient over its whole bounds.
+    GradientShape(Shape, Gradient<Content>),
+    /// A canvas drawn inside the layout. Scaled images grow into the space they are given,
+    /// and never take less than their own size unless it doesn't fit.
+    Image(Arc<Canvas<Content>>, ContentMode),
+    /// A line this many pixels thick, across the stack it's in (even inside modifiers like padding):
+    /// horizontal in a vertical stack, and vertical in a horizontal one. It takes all the space across the stack.
+    /// Outside of a stack, it's horizontal.
+    Divider(usize, Content)
 }
 
 impl<Content: Clone + Default + std::fmt::Debug, Ctx: Clone + std::fmt::Debug> Node<Content, Ctx> {
     pub fn plain_text(text: &str) -> Node<Content, Ctx> {
-        Node::Text(text.to_string(), Font::three_by_three(), Content::default())
+        Node::Text(text.to_string(), Font::three_by_three(), Content::default(), TextStyle::default())
     }
 
     pub fn text(text: &str, content: Content) -> Node<Content, Ctx> {
-        Node::Text(text.to_string(), Font::three_by_three(), content)
+        Node::Text(text.to_string(), Font::three_by_three(), content, TextStyle::default())
+    }
+
+    pub fn text_with_font<F: Into<Arc<Font>>>(text: &str, content: Content, font: F) -> Node<Content, Ctx> {
+        Node::Text(text.to_string(), font.into(), content, TextStyle::default())
+    }
+
+    /// A small icon, drawn with `content`.
+    pub fn icon(icon: Icon, content: Content) -> Node<Content, Ctx> {
+        Node::icon_with_size(icon, IconSize::Small, content)
+    }
+
+    pub fn icon_with_size(icon: Icon, size: IconSize, content: Content) -> Node<Content, Ctx> {
+        Node::Text(icon.char().to_string(), Font::icons(size), content, TextStyle::default())
+    }
+
+    /// Every glyph of `font`, in a grid of `columns` cells. Each glyph has the same character drawn
+    /// with `label_font` under it, to compare against. The printable Ascii characters are always included,
+    /// so the ones missing from the font show up with its unknown glyph.
+    pub fn glyph_sheet(font: Arc<Font>, label_font: Arc<Font>, content: Content, columns: usize) -> Node<Content, Ctx> {
+        let mut chars = font.chars();
+        chars.extend((b'!'..=b'~').map(char::from));
+        chars.sort();
+        chars.dedup();
+
+        let cell_width = font.glyph_size().width.max(label_font.glyph_size().width) + 2;
+
+        let cells: Vec<_> = chars
+            .into_iter()
+            .map(|c| {
+                Node::VerticalStack(alignment::HorizontalAlignment::Center, 1, vec![
+                    Node::text_with_font(&c.to_string(), content.clone(), font.clone()),
+                    Node::text_with_font(&c.to_string(), content.clone(), label_font.clone()),
+                ])
+                .width(cell_width)
+            })
+            .collect();
+
+        let rows = cells
+            .chunks(columns.max(1))
+            .map(|row| Node::HorizontalStack(alignment::VerticalAlignment::Top, 1, row.to_vec()))
+            .collect();
+
+        Node::VerticalStack(alignment::HorizontalAlignment::Left, 2, rows)
     }
 
-    pub fn text_with_font(text: &str, content: Content, font: &'static Font) -> Node<Content, Ctx> {
-        Node::Text(text.to_string(), font, content)
+    pub fn rich_text(text: RichText<Content>) -> Node<Content, Ctx> {
+        Node::RichText(text)
     }
 
     pub fn center(self) -> Node<Content, Ctx> {
@@ -170,6 +245,33 @@ impl<Content: Clone + Default + std::fmt::Debug, Ctx: Clone + std::fmt::Debug> N
         Node::Background(c.into(), Box::new(self))
     }
 
+    pub fn background_gradient(self, gradient: Gradient<Content>) -> Node<Content, Ctx> {
+        Node::GradientBackground(gradient, Box::new(self))
+    }
+
+    pub fn image<C: Into<Arc<Canvas<Content>>>>(canvas: C, mode: ContentMode) -> Node<Content, Ctx> {
+        Node::Image(canvas.into(), mode)
+    }
+
+    /// A one pixel thick divider, going across the stack it's in.
+    pub fn divider<C: Into<Content>>(content: C) -> Node<Content, Ctx> {
+        Node::Divider(1, content.into())
+    }
+
+    pub fn divider_with_thickness<C: Into<Content>>(thickness: usize, content: C) -> Node<Content, Ctx> {
+        Node::Divider(thickness, content.into())
+    }
+
+    /// Draws a blurred silhouette of this node behind it, moved by `offset`. It doesn't take any space.
+    pub fn shadow<C: Into<Content>>(self, radius: usize, offset: geometry::Vector, content: C) -> Node<Content, Ctx> {
+        Node::Effect(Effect::Shadow { radius, offset, content: content.into() }, Box::new(self))
+    }
+
+    /// Draws this node blurred, spreading each pixel `radius` pixels away.
+    pub fn blur(self, radius: usize) -> Node<Content, Ctx> {
+        Node::Effect(Effect::Blur(radius), Box::new(self))
+    }
+
     pub fn vertical_stack(nodes: Vec<Node<Content, Ctx>>) -> Node<Content, Ctx> {
         Node::VerticalStack(alignment::HorizontalAlignment::Center, 0, nodes)
     }
@@ -206,4 +308,155 @@ impl<Content: Clone + Default + std::fmt::Debug, Ctx: Clone + std::fmt::Debug> N
     pub fn as_overlay<DetachedContent: Fn() -> Node<Content, Ctx>>(self, content: DetachedContent) -> Node<Content, Ctx> {
         Node::Detached(Box::new(self), alignment::Alignment::center(), DetachedBehavior::Overlay, Box::new(content()))
     }
+
+    /// Draws every text in this node with its font scaled by `factor`.
+    pub fn font_scale(self, factor: usize) -> Node<Content, Ctx> {
+        self.map_fonts_cached(|font| font.scaled(factor))
+    }
+
+    /// Draws every text in this node with a synthetic bold version of its font.
+    pub fn bold(self) -> Node<Content, Ctx> {
+        self.map_fonts_cached(Font::bold)
+    }
+
+    /// Draws every text in this node with a synthetic italic version of its font.
+    pub fn italic(self) -> Node<Content, Ctx> {
+        self.map_fonts_cached(Font::italic)
+    }
+
+    /// Draws every text in this node with only the outline of its glyphs.
+    pub fn outlined(self) -> Node<Content, Ctx> {
+        self.map_fonts_cached(Font::outlined)
+    }
+
+    /// Draws a copy of every text in this node under it, one point down and to the right.
+    pub fn text_shadow<C: Into<Content>>(self, c: C) -> Node<Content, Ctx> {
+        let shadow = c.into();
+
+        self.map_texts(&mut |_, style| style.shadow = Some(shadow.clone()))
+    }
+
+    /// Underlines every line of every text in this node.
+    pub fn underline<C: Into<Content>>(self, c: C) -> Node<Content, Ctx> {
+        let underline = c.into();
+
+        self.map_texts(&mut |_, style| style.underline = Some(underline.clone()))
+    }
+
+    /// Strikes through every line of every text in this node.
+    pub fn strikethrough<C: Into<Content>>(self, c: C) -> Node<Content, Ctx> {
+        let strikethrough = c.into();
+
+        self.map_texts(&mut |_, style| style.strikethrough = Some(strikethrough.clone()))
+    }
+
+    /// Sets the contents used by the multi-color glyphs of every text in this node,
+    /// in the order of their `GlyphPoint::Palette` indices.
+    pub fn palette<C: Into<Content>>(self, palette: Vec<C>) -> Node<Content, Ctx> {
+        let palette: Vec<Content> = palette.into_iter().map(Into::into).collect();
+
+        self.map_texts(&mut |_, style| style.palette = palette.clone())
+    }
+
+    /// Sets the space between the glyphs of every text in this node, instead of the one from their fonts.
+    pub fn character_spacing(self, n: usize) -> Node<Content, Ctx> {
+        self.map_fonts_cached(|font| font.clone().with_character_spacing(n))
+    }
+
+    /// Sets the space between the lines of every text in this node, instead of the one from their fonts.
+    pub fn line_spacing(self, n: usize) -> Node<Content, Ctx> {
+        self.map_fonts_cached(|font| font.clone().with_line_spacing(n))
+    }
+
+    /// Sets how wide spaces are in every text in this node, instead of the width from their fonts.
+    pub fn space_width(self, n: usize) -> Node<Content, Ctx> {
+        self.map_fonts_cached(|font| font.clone().with_space_width(n))
+    }
+
+    /// Makes tabs in every text in this node move to the next multiple of `n` points.
+    pub fn tab_width(self, n: usize) -> Node<Content, Ctx> {
+        self.map_fonts_cached(|font| font.clone().with_tab_width(n))
+    }
+
+    /// Makes tabs in every text in this node move to the next of `stops` (in points from the start of the line).
+    pub fn tab_stops(self, stops: &[usize]) -> Node<Content, Ctx> {
+        self.map_fonts_cached(|font| font.clone().with_tab_stops(stops.to_vec()))
+    }
+
+    /// Like `map_fonts`, but creating each derived font only once, no matter how many texts share it.
+    fn map_fonts_cached<F: Fn(&Font) -> Font>(self, derive: F) -> Node<Content, Ctx> {
+        let mut derived_fonts: HashMap<*const Font, Arc<Font>> = HashMap::new();
+
+        self.map_fonts(&mut |font| {
+            derived_fonts
+                .entry(Arc::as_ptr(font))
+                .or_insert_with(|| Arc::new(derive(font)))
+                .clone()
+        })
+    }
+
+    /// Replaces the font of every text in this node with the result of `transform`.
+    /// Nodes built from the context (`WithContext`) are not reached, since they don't exist yet.
+    pub fn map_fonts<F: FnMut(&Arc<Font>) -> Arc<Font>>(self, transform: &mut F) -> Node<Content, Ctx> {
+        self.map_texts(&mut |font, _| *font = transform(font))
+    }
+
+    /// Changes the font and style of every text in this node.
+    /// Nodes built from the context (`WithContext`) are not reached, since they don't exist yet.
+    pub fn map_texts<F: FnMut(&mut Arc<Font>, &mut TextStyle<Content>)>(self, change: &mut F) -> Node<Content, Ctx> {
+        use Node::*;
+
+        let mut map = |node: Box<Node<Content, Ctx>>| Box::new(node.map_texts(change));
+
+        match self {
+            Text(text, mut font, content, mut style) => {
+                change(&mut font, &mut style);
+                Text(text, font, content, style)
+            }
+            RichText(mut text) => {
+                for span in text.spans_mut() {
+                    change(&mut span.font, &mut span.style);
+                }
+
+                RichText(text)
+            }
+            Width(n, node) => Width(n, map(node)),
+            Height(n, node) => Height(n, map(node)),
+            TopPadding(n, node) => TopPadding(n, map(node)),
+            RightPadding(n, node) => RightPadding(n, map(node)),
+            BottomPadding(n, node) => BottomPadding(n, map(node)),
+            LeftPadding(n, node) => LeftPadding(n, map(node)),
+            VCenter(node) => VCenter(map(node)),
+            HCenter(node) => HCenter(map(node)),
+            VBottomAlign(node) => VBottomAlign(map(node)),
+            HRightAlign(node) => HRightAlign(map(node)),
+            VTopAlign(node) => VTopAlign(map(node)),
+            HLeftAlign(node) => HLeftAlign(map(node)),
+            Background(c, node) => Background(c, map(node)),
+            GradientBackground(gradient, node) => GradientBackground(gradient, map(node)),
+            Effect(effect, node) => Effect(effect, map(node)),
+            Detached(wrapped, alignment, behavior, node) => {
+                let wrapped = map(wrapped);
+                Detached(wrapped, alignment, behavior, map(node))
+            }
+            TopBorder(n, c, node) => TopBorder(n, c, map(node)),
+            BottomBorder(n, c, node) => BottomBorder(n, c, map(node)),
+            LeftBorder(n, c, node) => LeftBorder(n, c, map(node)),
+            RightBorder(n, c, node) => RightBorder(n, c, map(node)),
+            VerticalStack(alignment, spacing, nodes) => {
+                VerticalStack(alignment, spacing, nodes.into_iter().map(|node| node.map_texts(change)).collect())
+            }
+            HorizontalStack(alignment, spacing, nodes) => {
+                HorizontalStack(alignment, spacing, nodes.into_iter().map(|node| node.map_texts(change)).collect())
+            }
+            NormalStack(alignment, nodes) => {
+                NormalStack(alignment, nodes.into_iter().map(|node| node.map_texts(change)).collect())
+            }
+            WithContext(f) => WithContext(f),
+            Shape(shape, behavior, content) => Shape(shape, behavior, content),
+            GradientShape(shape, gradient) => GradientShape(shape, gradient),
+            Image(canvas, mode) => Image(canvas, mode),
+            Divider(thickness, content) => Divider(thickness, content)
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,185 @@
+use super::geometry::Rect;
+
+/// A point along a path, in the coordinates of its `PathSpace`.
+pub type Point = (f32, f32);
+
+/// What the coordinates of a path are relative to, once it's drawn in bounds.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathSpace {
+    /// `(0.0, 0.0)` is the top left corner of the bounds and `(1.0, 1.0)` the bottom right,
+    /// so the path stretches with them.
+    Unit,
+    /// Pixels from the top left corner of the bounds. Whatever goes past them is cut off.
+    Pixels
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathSegment {
+    MoveTo(Point),
+    LineTo(Point),
+    /// A quadratic curve through a control point to the end point.
+    QuadTo(Point, Point),
+    /// A cubic curve through two control points to the end point.
+    CubicTo(Point, Point, Point),
+    Close
+}
+
+/// An outline to draw as a shape, built one segment at a time, like
+/// `Path::unit().move_to(0.5, 0.0).line_to(1.0, 1.0).line_to(0.0, 1.0).close()` for a triangle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path {
+    space: PathSpace,
+    segments: Vec<PathSegment>
+}
+
+impl Path {
+    pub fn new(space: PathSpace) -> Self {
+        Path { space, segments: vec![] }
+    }
+
+    /// A path in unit coordinates of the bounds it's drawn in.
+    pub fn unit() -> Self {
+        Self::new(PathSpace::Unit)
+    }
+
+    /// A path in pixels from the top left corner of the bounds it's drawn in.
+    pub fn pixels() -> Self {
+        Self::new(PathSpace::Pixels)
+    }
+
+    pub fn space(&self) -> &PathSpace {
+        &self.space
+    }
+
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Starts a new part of the path at `x`, `y`.
+    pub fn move_to(mut self, x: f32, y: f32) -> Self {
+        self.segments.push(PathSegment::MoveTo((x, y)));
+
+        self
+    }
+
+    pub fn line_to(mut self, x: f32, y: f32) -> Self {
+        self.segments.push(PathSegment::LineTo((x, y)));
+
+        self
+    }
+
+    pub fn quad_to(mut self, control: Point, end: Point) -> Self {
+        self.segments.push(PathSegment::QuadTo(control, end));
+
+        self
+    }
+
+    pub fn cubic_to(mut self, first_control: Point, second_control: Point, end: Point) -> Self {
+        self.segments.push(PathSegment::CubicTo(first_control, second_control, end));
+
+        self
+    }
+
+    /// Follows the ellipse around `center` with `radius` along each axis, from `start_angle`
+    /// and going `sweep` degrees. Angles go clockwise, from the right of the center.
+    ///
+    /// A line goes from where the path is to the start of the arc, and the arc starts the path if it's empty.
+    pub fn arc(mut self, center: Point, radius: (f32, f32), start_angle: f32, sweep: f32) -> Self {
+        let point_at = |angle: f32| (center.0 + radius.0 * angle.cos(), center.1 + radius.1 * angle.sin());
+
+        let start = start_angle.to_radians();
+        let start_point = point_at(start);
+
+        self.segments.push(match self.segments.last() {
+            None | Some(PathSegment::Close) => PathSegment::MoveTo(start_point),
+            Some(_) => PathSegment::LineTo(start_point)
+        });
+
+        // Cubic curves stay close to an ellipse for up to a quarter of it.
+        let parts = (sweep.abs() / 90.0).ceil().max(1.0) as usize;
+        let step = sweep.to_radians() / parts as f32;
+        // How far the control points are along the tangents, for a part of `step` radians.
+        let tangent = 4.0 / 3.0 * (step / 4.0).tan();
+
+        for part in 0..parts {
+            let from = start + step * part as f32;
+            let to = from + step;
+
+            let first_control = (
+                center.0 + radius.0 * (from.cos() - tangent * from.sin()),
+                center.1 + radius.1 * (from.sin() + tangent * from.cos())
+            );
+            let second_control = (
+                center.0 + radius.0 * (to.cos() + tangent * to.sin()),
+                center.1 + radius.1 * (to.sin() - tangent * to.cos())
+            );
+
+            self.segments.push(PathSegment::CubicTo(first_control, second_control, point_at(to)));
+        }
+
+        self
+    }
+
+    /// Goes back to where the current part of the path started.
+    pub fn close(mut self) -> Self {
+        self.segments.push(PathSegment::Close);
+
+        self
+    }
+
+    /// The path placed in `bounds`, with the origin at their top left corner.
+    /// Paths without anything to draw have no outline.
+    pub(crate) fn outline(&self, bounds: &Rect) -> Option<tiny_skia::Path> {
+        let (scale_x, scale_y) = match self.space {
+            PathSpace::Unit => (bounds.width as f32, bounds.height as f32),
+            PathSpace::Pixels => (1.0, 1.0)
+        };
+        let scaled = |(x, y): Point| (x * scale_x, y * scale_y);
+
+        let mut builder = tiny_skia::PathBuilder::new();
+
+        for segment in &self.segments {
+            match *segment {
+                PathSegment::MoveTo(point) => {
+                    let (x, y) = scaled(point);
+                    builder.move_to(x, y);
+                }
+                PathSegment::LineTo(point) => {
+                    let (x, y) = scaled(point);
+                    builder.line_to(x, y);
+                }
+                PathSegment::QuadTo(control, end) => {
+                    let (control, end) = (scaled(control), scaled(end));
+                    builder.quad_to(control.0, control.1, end.0, end.1);
+                }
+                PathSegment::CubicTo(first_control, second_control, end) => {
+                    let (first, second, end) = (scaled(first_control), scaled(second_control), scaled(end));
+                    builder.cubic_to(first.0, first.1, second.0, second.1, end.0, end.1);
+                }
+                PathSegment::Close => builder.close()
+            }
+        }
+
+        builder.finish()
+    }
+}
+
+#[test]
+fn builds_arcs_from_curves() {
+    let path = Path::unit().move_to(0.5, 0.5).arc((0.5, 0.5), (0.5, 0.5), 0.0, 180.0).close();
+
+    // The line to the start of the arc, and two quarters of it.
+    assert_eq!(path.segments().len(), 5);
+    assert_eq!(path.segments()[1], PathSegment::LineTo((1.0, 0.5)));
+
+    let PathSegment::CubicTo(_, _, end) = path.segments()[3] else { panic!("Arcs should be made of cubic curves") };
+    assert!((end.0 - 0.0).abs() < 0.001 && (end.1 - 0.5).abs() < 0.001);
+
+    let outline = path.outline(&Rect::sized(10, 20)).unwrap();
+    let bounds = outline.bounds();
+    let edges = [bounds.left(), bounds.top(), bounds.right(), bounds.bottom()].map(f32::round);
+
+    // The bottom half of the bounds, stretched to them.
+    assert_eq!(edges, [0.0, 10.0, 10.0, 20.0]);
+    assert!(Path::pixels().outline(&Rect::sized(10, 10)).is_none());
+}
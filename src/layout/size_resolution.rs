@@ -1,17 +1,19 @@
-use std::{cmp::Ordering, fmt::Debug};
+use std::{fmt::Debug, sync::Arc};
 
 use crate::{
+    fonts::{font_calculations::ResolvedText, style::TextStyle, Font},
+    graphics::canvas::Canvas,
     layout::{
         self,
         alignment::Edge,
-        node::{DetachedBehavior, ShapeBehavior},
+        node::{ContentMode, DetachedBehavior, ShapeBehavior},
         sizing::{self, ItemSizing, Sizing},
     },
     rendering::DrawCommand,
 };
 
 use super::{
-    geometry::Rect,
+    geometry::{Rect, Size},
     node::Node,
     sized_node::{SizedItem, SizedNode},
 };
@@ -25,15 +27,27 @@ impl SizeCalculator {
         container_node: &Node<Content, Ctx>,
         bounds: &Rect,
         context: &mut Ctx,
+    ) -> SizedNode<Content> {
+        Self::resolve_size_in(container_node, bounds, context, None)
+    }
+
+    /// Resolves the size of a node inside of a stack going along `stack`, through any modifiers around it.
+    /// Dividers go across it.
+    fn resolve_size_in<Content: Clone + Default + Debug, Ctx: Clone + std::fmt::Debug>(
+        container_node: &Node<Content, Ctx>,
+        bounds: &Rect,
+        context: &mut Ctx,
+        stack: Option<StackAxis>,
     ) -> SizedNode<Content> {
         use sizing::Sizing::*;
         use Node::*;
 
         match container_node {
-            Text(t, font, content) => {
-                let resolved_text = font.calculate_lines(t, bounds);
-                let width = resolved_text.size().width;
-                let height = resolved_text.size().height;
+            Text(t, font, content, style) => {
+                let extra_size = style.extra_size(font);
+                let resolved_text = font.calculate_lines(t, &text_bounds(bounds, &extra_size));
+                let width = resolved_text.size().width + extra_size.width;
+                let height = resolved_text.size().height + extra_size.height;
 
                 // FIXME: Having a layered layout causes this code to be called way too many times.
                 // maybe my approach with borders and paddings is a little too much.
@@ -45,10 +59,20 @@ impl SizeCalculator {
                 // TODO: Introduce `Flexible` item sizing to handle better text sizing.
                 let sizing = sizing::ItemSizing::new(Static(width), Static(height));
 
-                SizedNode::new(SizedItem::Text(t.clone(), content.clone(), font), sizing)
+                SizedNode::new(SizedItem::Text(t.clone(), content.clone(), font.clone(), style.clone()), sizing)
+            }
+            RichText(text) => {
+                let extra_size = text.extra_size();
+                let resolved_text = text.calculate_lines(&text_bounds(bounds, &extra_size));
+                let width = resolved_text.size().width + extra_size.width;
+                let height = resolved_text.size().height + extra_size.height;
+
+                let sizing = sizing::ItemSizing::new(Static(width), Static(height));
+
+                SizedNode::new(SizedItem::RichText(text.clone()), sizing)
             }
             VCenter(node) => {
-                let resolved = Self::resolve_size(node, bounds, context);
+                let resolved = Self::resolve_size_in(node, bounds, context, stack);
                 let content_size = resolved.sizing.clone();
 
                 let min_height = content_size.vertical.min_content_size();
@@ -58,7 +82,7 @@ impl SizeCalculator {
                 SizedNode::new(SizedItem::VCenter(resolved), sizing)
             }
             VBottomAlign(node) => {
-                let resolved = Self::resolve_size(node, bounds, context);
+                let resolved = Self::resolve_size_in(node, bounds, context, stack);
                 let content_size = resolved.sizing.clone();
 
                 let min_height = content_size.vertical.min_content_size();
@@ -71,7 +95,7 @@ impl SizeCalculator {
                 SizedNode::new(SizedItem::VBottomAlign(resolved), sizing)
             }
             HCenter(node) => {
-                let resolved = Self::resolve_size(node, bounds, context);
+                let resolved = Self::resolve_size_in(node, bounds, context, stack);
                 let content_size = resolved.sizing.clone();
 
                 let min_width = content_size.horizontal.min_content_size();
@@ -84,7 +108,7 @@ impl SizeCalculator {
                 SizedNode::new(SizedItem::HCenter(resolved), sizing)
             }
             HRightAlign(node) => {
-                let resolved = Self::resolve_size(node, bounds, context);
+                let resolved = Self::resolve_size_in(node, bounds, context, stack);
                 let content_size = resolved.sizing.clone();
 
                 let min_width = content_size.horizontal.min_content_size();
@@ -97,7 +121,7 @@ impl SizeCalculator {
                 SizedNode::new(SizedItem::HRightAlign(resolved), sizing)
             }
             VTopAlign(node) => {
-                let resolved = Self::resolve_size(node, bounds, context);
+                let resolved = Self::resolve_size_in(node, bounds, context, stack);
                 let content_size = resolved.sizing.clone();
 
                 let min_height = content_size.vertical.min_content_size();
@@ -110,7 +134,7 @@ impl SizeCalculator {
                 SizedNode::new(SizedItem::VTopAlign(resolved), sizing)
             }
             HLeftAlign(node) => {
-                let resolved = Self::resolve_size(node, bounds, context);
+                let resolved = Self::resolve_size_in(node, bounds, context, stack);
                 let content_size = resolved.sizing.clone();
 
                 let min_width = content_size.horizontal.min_content_size();
@@ -126,7 +150,7 @@ impl SizeCalculator {
                 let mut bounds = bounds.clone();
                 bounds.width = *size;
 
-                let resolved_content = Self::resolve_size(node, &bounds, context);
+                let resolved_content = Self::resolve_size_in(node, &bounds, context, stack);
                 let mut frame = resolved_content.sizing.clone();
                 frame.horizontal = Static(*size);
 
@@ -136,14 +160,14 @@ impl SizeCalculator {
                 let mut bounds = bounds.clone();
                 bounds.height = *size;
 
-                let resolved_content = Self::resolve_size(node, &bounds, context);
+                let resolved_content = Self::resolve_size_in(node, &bounds, context, stack);
                 let mut frame = resolved_content.sizing.clone();
                 frame.vertical = Static(*size);
 
                 SizedNode::new(SizedItem::Height(*size, resolved_content), frame)
             }
             TopPadding(n, node) | BottomPadding(n, node) => {
-                let resolved = Self::resolve_size(node, bounds, context);
+                let resolved = Self::resolve_size_in(node, bounds, context, stack);
                 let mut frame = resolved.sizing.clone();
 
                 frame.vertical.clamped_add(*n);
@@ -159,7 +183,7 @@ impl SizeCalculator {
                     let mut bounds = bounds.clone();
                     bounds.height = bounds.height.saturating_sub(*n);
 
-                    let resolved_content = Self::resolve_size(node, &bounds, context);
+                    let resolved_content = Self::resolve_size_in(node, &bounds, context, stack);
                     let mut frame = resolved_content.sizing.clone();
 
                     frame.vertical.clamped_add(*n);
@@ -170,7 +194,7 @@ impl SizeCalculator {
                 }
             }
             LeftPadding(n, node) | RightPadding(n, node) => {
-                let resolved = Self::resolve_size(node, bounds, context);
+                let resolved = Self::resolve_size_in(node, bounds, context, stack);
                 let mut frame = resolved.sizing.clone();
 
                 let make_node = |n: usize, node: SizedNode<Content>| match container_node {
@@ -185,7 +209,7 @@ impl SizeCalculator {
                     let mut bounds = bounds.clone();
                     bounds.width = bounds.width.saturating_sub(*n);
 
-                    let resolved_content = Self::resolve_size(node, &bounds, context);
+                    let resolved_content = Self::resolve_size_in(node, &bounds, context, stack);
                     frame = resolved_content.sizing.clone();
                     frame.horizontal.clamped_add(*n);
 
@@ -197,14 +221,26 @@ impl SizeCalculator {
                 }
             }
             Background(c, node) => {
-                let resolved_content = Self::resolve_size(node, bounds, context);
+                let resolved_content = Self::resolve_size_in(node, bounds, context, stack);
                 let frame = resolved_content.sizing.clone();
 
                 SizedNode::new(SizedItem::Background(c.clone(), resolved_content), frame)
             }
+            GradientBackground(gradient, node) => {
+                let resolved_content = Self::resolve_size_in(node, bounds, context, stack);
+                let frame = resolved_content.sizing.clone();
+
+                SizedNode::new(SizedItem::GradientBackground(gradient.clone(), resolved_content), frame)
+            }
+            Effect(effect, node) => {
+                let resolved_content = Self::resolve_size_in(node, bounds, context, stack);
+                let frame = resolved_content.sizing.clone();
+
+                SizedNode::new(SizedItem::Effect(effect.clone(), resolved_content), frame)
+            }
             TopBorder(n, c, node) => {
                 let outer_bounds = bounds;
-                let mut resolved_content = Self::resolve_size(node, outer_bounds, context);
+                let mut resolved_content = Self::resolve_size_in(node, outer_bounds, context, stack);
                 let mut frame = resolved_content.sizing.clone();
 
                 let added_height = *n;
@@ -215,7 +251,7 @@ impl SizeCalculator {
                     let mut bounds = outer_bounds.clone();
                     bounds.height = bounds.height.saturating_sub(added_height);
 
-                    resolved_content = Self::resolve_size(node, &bounds, context);
+                    resolved_content = Self::resolve_size_in(node, &bounds, context, stack);
                     frame = resolved_content.sizing.clone();
 
                     frame.vertical.clamped_add(added_height);
@@ -228,7 +264,7 @@ impl SizeCalculator {
             }
             BottomBorder(n, c, node) => {
                 let outer_bounds = bounds;
-                let mut resolved_content = Self::resolve_size(node, outer_bounds, context);
+                let mut resolved_content = Self::resolve_size_in(node, outer_bounds, context, stack);
                 let mut frame = resolved_content.sizing.clone();
 
                 let added_height = *n;
@@ -239,7 +275,7 @@ impl SizeCalculator {
                     let mut bounds = outer_bounds.clone();
                     bounds.height = bounds.height.saturating_sub(added_height);
 
-                    resolved_content = Self::resolve_size(node, &bounds, context);
+                    resolved_content = Self::resolve_size_in(node, &bounds, context, stack);
                     frame = resolved_content.sizing.clone();
 
                     frame.vertical.clamped_add(added_height);
@@ -252,7 +288,7 @@ impl SizeCalculator {
             }
             LeftBorder(n, c, node) => {
                 let outer_bounds = bounds;
-                let mut resolved_content = Self::resolve_size(node, outer_bounds, context);
+                let mut resolved_content = Self::resolve_size_in(node, outer_bounds, context, stack);
                 let mut frame = resolved_content.sizing.clone();
 
                 let added_width = *n;
@@ -263,7 +299,7 @@ impl SizeCalculator {
                     let mut bounds = outer_bounds.clone();
                     bounds.width = bounds.width.saturating_sub(added_width);
 
-                    resolved_content = Self::resolve_size(node, &bounds, context);
+                    resolved_content = Self::resolve_size_in(node, &bounds, context, stack);
                     frame = resolved_content.sizing.clone();
 
                     frame.horizontal.clamped_add(added_width);
@@ -276,7 +312,7 @@ impl SizeCalculator {
             }
             RightBorder(n, c, node) => {
                 let outer_bounds = bounds;
-                let mut resolved_content = Self::resolve_size(node, outer_bounds, context);
+                let mut resolved_content = Self::resolve_size_in(node, outer_bounds, context, stack);
                 let mut frame = resolved_content.sizing.clone();
 
                 let added_width = *n;
@@ -287,7 +323,7 @@ impl SizeCalculator {
                     let mut bounds = outer_bounds.clone();
                     bounds.width = bounds.width.saturating_sub(added_width);
 
-                    resolved_content = Self::resolve_size(node, &bounds, context);
+                    resolved_content = Self::resolve_size_in(node, &bounds, context, stack);
                     frame = resolved_content.sizing.clone();
 
                     frame.horizontal.clamped_add(added_width);
@@ -310,7 +346,7 @@ impl SizeCalculator {
                 let mut resolved_children: Vec<SizedNode<_>> = vec![];
 
                 for node in nodes {
-                    let resolved_node = Self::resolve_size(node, &bounds, context);
+                    let resolved_node = Self::resolve_size_in(node, &bounds, context, Some(StackAxis::Vertical));
                     let node_sizing = resolved_node.sizing.clone();
                     result.horizontal = match result.horizontal {
                         Static(j) => match node_sizing.horizontal {
@@ -351,7 +387,7 @@ impl SizeCalculator {
                 let mut resolved_children = vec![];
 
                 for node in nodes {
-                    let resolved_node = Self::resolve_size(node, &bounds, context);
+                    let resolved_node = Self::resolve_size_in(node, &bounds, context, Some(StackAxis::Horizontal));
                     let node_sizing = resolved_node.sizing.clone();
                     result.vertical = match result.vertical {
                         Static(j) => match node_sizing.vertical {
@@ -376,6 +412,24 @@ impl SizeCalculator {
                     resolved_children.push(resolved_node);
                 }
 
+                // Lining up baselines moves items down, which can make the stack taller than its tallest item.
+                if let Some(last) = baseline_edge(alignment) {
+                    let items: Vec<_> = resolved_children
+                        .iter()
+                        .map(|node| (node, node.sizing.fit_into(&bounds)))
+                        .collect();
+
+                    let height = baseline_offsets(&items, last)
+                        .into_iter()
+                        .zip(&items)
+                        .map(|(offset, (_, item_bounds))| offset + item_bounds.height)
+                        .max()
+                        .unwrap_or(0);
+
+                    let missing_height = height.saturating_sub(result.vertical.min_content_size());
+                    result.vertical.clamped_add(missing_height);
+                }
+
                 SizedNode::new(
                     SizedItem::HorizontalStack(alignment.clone(), *spacing, resolved_children),
                     result,
@@ -390,7 +444,7 @@ impl SizeCalculator {
                 let mut resolved_children = vec![];
 
                 for node in nodes {
-                    let resolved_node = Self::resolve_size(node, &bounds, context);
+                    let resolved_node = Self::resolve_size_in(node, bounds, context, None);
                     let node_sizing = resolved_node.sizing.clone();
                     
                     result.vertical = match result.vertical {
@@ -436,12 +490,12 @@ impl SizeCalculator {
             WithContext(node) => {
                 let node = node(context);
 
-                Self::resolve_size(&node, bounds, context)
+                Self::resolve_size_in(&node, bounds, context, stack)
             }
 
             Detached(wrapped_content, alignment, behavior, content) => {
-                let wrapped_sized = Self::resolve_size(&*wrapped_content, bounds, context);
-                let content_sized = Self::resolve_size(&content, bounds, context);
+                let wrapped_sized = Self::resolve_size_in(wrapped_content, bounds, context, stack);
+                let content_sized = Self::resolve_size_in(content, bounds, context, None);
 
                 let wrapped_sizing = wrapped_sized.sizing.clone();
 
@@ -463,10 +517,45 @@ impl SizeCalculator {
                     sizing,
                 )
             }
+            GradientShape(shape, gradient) => {
+                let sizing = ItemSizing::new(Sizing::Flexible(0), Sizing::Flexible(0));
+
+                SizedNode::new(SizedItem::GradientShape(shape.clone(), gradient.clone()), sizing)
+            }
+            Image(canvas, mode) => {
+                // Images bigger than the bounds are scaled down, or cut when they aren't scaled.
+                let width = canvas.size.width.min(bounds.width);
+                let height = canvas.size.height.min(bounds.height);
+
+                let sizing = match mode {
+                    ContentMode::Center => ItemSizing::new(Static(width), Static(height)),
+                    ContentMode::Fit | ContentMode::Fill | ContentMode::Stretch => {
+                        ItemSizing::new(Flexible(width), Flexible(height))
+                    }
+                };
+
+                SizedNode::new(SizedItem::Image(canvas.clone(), *mode), sizing)
+            }
+            Divider(thickness, content) => {
+                // Across the stack it's in, and horizontal outside of one.
+                let sizing = match stack {
+                    Some(StackAxis::Horizontal) => ItemSizing::new(Static(*thickness), Greedy(0)),
+                    Some(StackAxis::Vertical) | None => ItemSizing::new(Greedy(0), Static(*thickness)),
+                };
+
+                SizedNode::new(SizedItem::Divider(content.clone()), sizing)
+            }
         }
     }
 }
 
+/// The direction a stack lays out its items in.
+#[derive(Clone, Copy)]
+enum StackAxis {
+    Vertical,
+    Horizontal
+}
+
 // Resolve size
 #[derive(Default)]
 pub struct SizeResolver;
@@ -480,37 +569,21 @@ impl SizeResolver {
         let layout = sized_node.clone();
 
         match *layout.node {
-            Text(text, content, font) => {
-                let mut commands = vec![];
-                let resolved_text = font.calculate_lines(&text, bounds);
-
-                for line in resolved_text.lines() {
-                    let line_bounds = Rect::new(
-                        bounds.x + line.bounds().x, // FIXME: Left align only at the moment
-                        bounds.y + line.bounds().y,
-                        line.bounds().size().width,
-                        line.bounds().size().height,
-                    );
-
-                    for resolved_glyph in &line.glyphs {
-                        let glyph = resolved_glyph.glyph();
-                        let size = resolved_glyph.size();
-                        let offset = resolved_glyph.offset();
-
-                        commands.push(DrawCommand::Bitmap(
-                            glyph.map(|b| if b { Some(content.clone()) } else { None }),
-                            Rect::new(
-                                offset.width as i64 + line_bounds.x,
-                                offset.height as i64 + line_bounds.y,
-                                size.width,
-                                size.height,
-                            ),
-                        ));
-                    }
-                }
+            Text(text, content, font, style) => {
+                let extra_size = style.extra_size(&font);
+                let resolved_text = font.calculate_lines(&text, &text_bounds(bounds, &extra_size));
 
-                // TODO: Convert text into text lines (split by new line, handle when a line wraps...)
-                commands
+                Self::resolve_text_commands(&text, &resolved_text, &[(&font, &content, &style)], bounds)
+            }
+            RichText(text) => {
+                let resolved_text = text.calculate_lines(&text_bounds(bounds, &text.extra_size()));
+
+                let spans: Vec<_> = text.spans()
+                    .iter()
+                    .map(|span| (&span.font, &span.content, &span.style))
+                    .collect();
+
+                Self::resolve_text_commands(text.text(), &resolved_text, &spans, bounds)
             }
             Width(_, node) | Height(_, node) => {
                 let frame = node.sizing.fit_into(bounds);
@@ -613,6 +686,23 @@ impl SizeResolver {
 
                 commands
             }
+            GradientBackground(gradient, node) => {
+                let mut frame = node.sizing.fit_into(bounds);
+                frame.x = bounds.x;
+                frame.y = bounds.y;
+
+                let mut commands = vec![DrawCommand::FillGradient(bounds.clone(), layout::geometry::Shape::Rectangle, gradient)];
+
+                commands.extend(Self::resolve_draw_commands(&node, &frame));
+
+                commands
+            }
+            Effect(effect, node) => {
+                let frame = node.sizing.fit_into(bounds);
+                let commands = Self::resolve_draw_commands(&node, &frame);
+
+                vec![DrawCommand::Effect(frame, effect, commands)]
+            }
             Border(n, border_style, edges, node) => {
                 let outer_bounds = bounds;
                 let mut inner_bounds = bounds.clone();
@@ -698,380 +788,854 @@ impl SizeResolver {
 
                 commands
             }
-            VerticalStack(alignment, spacing, nodes) => {
-                let mut max_width = 0usize;
+            VerticalStack(alignment, spacing, nodes) => Self::vertical_stack_frames(&alignment, spacing, nodes, bounds)
+                .into_iter()
+                .flat_map(|(node, frame)| Self::resolve_draw_commands(&node, &frame))
+                .collect(),
+            HorizontalStack(alignment, spacing, nodes) => Self::horizontal_stack_frames(&alignment, spacing, nodes, bounds)
+                .into_iter()
+                .flat_map(|(node, frame)| Self::resolve_draw_commands(&node, &frame))
+                .collect(),
+            NormalStack(alignment, nodes) => Self::normal_stack_frames(&alignment, nodes, bounds)
+                .into_iter()
+                .flat_map(|(node, frame)| Self::resolve_draw_commands(&node, &frame))
+                .collect(),
+            Detached(wrapped_content, alignment, behavior, node) => {
+                // FIXME: Alignment not handled
+                let mut result: Vec<DrawCommand<Content>> = vec![];
+                
+                let wrapped_bounds = wrapped_content.sizing.fit_into(bounds);
+                let mut content_bounds = node.sizing.fit_into(&wrapped_bounds);
 
-                let spacing_sizing = spacing * (nodes.len().saturating_sub(1));
+                match &alignment.horizontal() {
+                    layout::alignment::HorizontalAlignment::Left => { /* Already aligned to the left */}
+                    layout::alignment::HorizontalAlignment::Center => {
+                        let center = wrapped_bounds.width / 2;
+                        let start = center - content_bounds.width/2;
+                        content_bounds.x += start as i64;
+                    }
+                    layout::alignment::HorizontalAlignment::Right => {
+                        let right = wrapped_bounds.width;
+                        let start = right - content_bounds.width;
+                        content_bounds.x += start as i64;
+                    }
+                }
 
-                let mut last_bounds = Rect::zero();
+                match &alignment.vertical() {
+                    // A single detached item has no other baseline to line up with.
+                    layout::alignment::VerticalAlignment::Top
+                    | layout::alignment::VerticalAlignment::FirstBaseline
+                    | layout::alignment::VerticalAlignment::LastBaseline => { /* Already aligned to the left */}
+                    layout::alignment::VerticalAlignment::Center => {
+                        let center = wrapped_bounds.height / 2;
+                        let start = center - content_bounds.height/2;
+                        content_bounds.y += start as i64;
+                    }
+                    layout::alignment::VerticalAlignment::Bottom => {
+                        let bottom = wrapped_bounds.height;
+                        let start = bottom - content_bounds.height;
+                        content_bounds.y += start as i64;
+                    }
+                }
 
-                let mut greedy_count = 0;
-                let mut expandable_count = 0;
-                let mut static_height = spacing_sizing;
+                let mut detached_commands = Self::resolve_draw_commands(&node, &content_bounds);
+                let mut wrapped_commands = Self::resolve_draw_commands(&wrapped_content, &wrapped_bounds);
 
-                for node in &nodes {
-                    match node.sizing.vertical {
-                        Sizing::Static(n) => static_height += n,
-                        Sizing::Greedy(_) => {
-                            expandable_count += 1;
-                            greedy_count += 1;
-                        }
-                        Sizing::Flexible(_) => {
-                            expandable_count += 1;
-                        }
+                match behavior {
+                    DetachedBehavior::Background => {
+                        result.append(&mut detached_commands);
+                        result.append(&mut wrapped_commands);
+                    }
+                    DetachedBehavior::Overlay => {
+                        result.append(&mut wrapped_commands);
+                        result.append(&mut detached_commands);
                     }
                 }
 
-                let mut greedy_space = bounds.height.saturating_sub(static_height);
+                result
+            }
+            Shape(shape, shape_behavior, content) => match shape_behavior {
+                ShapeBehavior::Fill => vec![DrawCommand::FillShape(bounds.clone(), shape, content)],
+                ShapeBehavior::Stroke(n) => {
+                    vec![DrawCommand::StrokeShape(bounds.clone(), n, shape, content)]
+                }
+            },
+            GradientShape(shape, gradient) => vec![DrawCommand::FillGradient(bounds.clone(), shape, gradient)],
+            Image(canvas, mode) => {
+                let frame = sized_node.sizing.fit_into(bounds);
 
-                let greedy_size = if greedy_count == 0 {
-                    if expandable_count == 0 {
-                        0
-                    } else {
-                        greedy_space / expandable_count
-                    }
-                } else {
-                    greedy_space / greedy_count
-                };
+                vec![DrawCommand::Bitmap(image_bitmap(&canvas, mode, &frame.size()), frame)]
+            }
+            Divider(content) => vec![DrawCommand::FillRect(sized_node.sizing.fit_into(bounds), content)],
+        }
+    }
 
-                let mut new_nodes = vec![];
+    /// Where each item of a vertical stack is drawn, when the stack is drawn in `bounds`.
+    fn vertical_stack_frames<Content: Clone + Default + Debug>(
+        alignment: &layout::alignment::HorizontalAlignment,
+        spacing: usize,
+        nodes: Vec<SizedNode<Content>>,
+        bounds: &Rect,
+    ) -> Vec<(SizedNode<Content>, Rect)> {
+        let mut max_width = 0usize;
 
-                for node in &nodes {
-                    let mut n = (*node).clone();
-                    use layout::sizing::Sizing;
+        let spacing_sizing = spacing * (nodes.len().saturating_sub(1));
 
-                    n.sizing.vertical = match n.sizing.vertical {
-                        Sizing::Static(sz) => Sizing::Static(sz),
-                        Sizing::Flexible(sz) if greedy_count != 0 => {
-                            // When there's some greedy node, they take priority. Which means the flexible item
-                            // cannot grow.
-                            // TODO: When greedy sizing gets a max value implemented, this will need to change.
-                            Sizing::Static(sz)
-                        }
-                        Sizing::Greedy(tight) | Sizing::Flexible(tight) => {
-                            greedy_space -= greedy_size;
-                            let mut node_height = greedy_size;
-                            if greedy_space < greedy_size {
-                                node_height += greedy_space;
-                                greedy_space = 0;
-                            }
+        let mut last_bounds = Rect::zero();
 
-                            Sizing::Static(node_height.max(tight))
-                        }
-                    };
+        let mut greedy_count = 0;
+        let mut expandable_count = 0;
+        let mut static_height = spacing_sizing;
 
-                    new_nodes.push(n);
+        for node in &nodes {
+            match node.sizing.vertical {
+                Sizing::Static(n) => static_height += n,
+                Sizing::Greedy(_) => {
+                    expandable_count += 1;
+                    greedy_count += 1;
+                }
+                Sizing::Flexible(_) => {
+                    expandable_count += 1;
                 }
+            }
+        }
 
-                let nodes = new_nodes;
+        let mut greedy_space = bounds.height.saturating_sub(static_height);
 
-                let mut raw_bounds = vec![];
-                for node in &nodes {
-                    let size = node.sizing.fit_into(bounds);
+        let greedy_size = greedy_space
+            .checked_div(greedy_count)
+            .or_else(|| greedy_space.checked_div(expandable_count))
+            .unwrap_or(0);
 
-                    let spacing_offset = if raw_bounds.is_empty() {
-                        0
-                    } else {
-                        spacing as i64
-                    };
+        let mut new_nodes = vec![];
 
-                    let node_bounds = Rect::new(
-                        0,
-                        last_bounds.max_y() + spacing_offset,
-                        size.width,
-                        size.height,
-                    );
-                    last_bounds = node_bounds.clone();
+        for node in &nodes {
+            let mut n = (*node).clone();
+            use layout::sizing::Sizing;
 
-                    if node_bounds.width > max_width {
-                        max_width = node_bounds.width;
+            n.sizing.vertical = match n.sizing.vertical {
+                Sizing::Static(sz) => Sizing::Static(sz),
+                Sizing::Flexible(sz) if greedy_count != 0 => {
+                    // When there's some greedy node, they take priority. Which means the flexible item
+                    // cannot grow.
+                    // TODO: When greedy sizing gets a max value implemented, this will need to change.
+                    Sizing::Static(sz)
+                }
+                Sizing::Greedy(tight) | Sizing::Flexible(tight) => {
+                    greedy_space -= greedy_size;
+                    let mut node_height = greedy_size;
+                    if greedy_space < greedy_size {
+                        node_height += greedy_space;
+                        greedy_space = 0;
                     }
 
-                    raw_bounds.push(node_bounds);
+                    Sizing::Static(node_height.max(tight))
                 }
+            };
 
-                let final_bounds: Vec<_> = raw_bounds.into_iter().map(|mut bound| {
-                    match &alignment {
-                        layout::alignment::HorizontalAlignment::Left => { /* Already aligned to the left */}
-                        layout::alignment::HorizontalAlignment::Center => {
-                            let center = max_width / 2;
-                            let start = center - bound.width/2;
-                            bound.x = start as i64;
-                        }
-                        layout::alignment::HorizontalAlignment::Right => {
-                            let right = max_width;
-                            let start = right - bound.width;
-                            bound.x = start as i64;
-                        }
-                    }
+            new_nodes.push(n);
+        }
+
+        let nodes = new_nodes;
 
-                    // move from 0 based bounds to the actual frame of the container
-                    bound.x += bounds.x;
-                    bound.y += bounds.y;
+        let mut raw_bounds = vec![];
+        for node in &nodes {
+            let size = node.sizing.fit_into(bounds);
 
-                    bound
-                }).collect();
+            let spacing_offset = if raw_bounds.is_empty() {
+                0
+            } else {
+                spacing as i64
+            };
 
-                nodes
-                    .into_iter()
-                    .enumerate()
-                    .flat_map(|(i, node)| {
-                        let size = &final_bounds[i];
+            let node_bounds = Rect::new(
+                0,
+                last_bounds.max_y() + spacing_offset,
+                size.width,
+                size.height,
+            );
+            last_bounds = node_bounds.clone();
 
-                        Self::resolve_draw_commands(&node, size)
-                    })
-                    .collect::<Vec<_>>()
+            if node_bounds.width > max_width {
+                max_width = node_bounds.width;
             }
-            HorizontalStack(alignment, spacing, nodes) => {
-                let mut max_height = 0usize;
 
-                let spacing_sizing = spacing * (nodes.len().saturating_sub(1));
+            raw_bounds.push(node_bounds);
+        }
 
-                let mut last_bounds = Rect::zero();
+        let final_bounds: Vec<_> = raw_bounds.into_iter().map(|mut bound| {
+            match alignment {
+                layout::alignment::HorizontalAlignment::Left => { /* Already aligned to the left */}
+                layout::alignment::HorizontalAlignment::Center => {
+                    let center = max_width / 2;
+                    let start = center - bound.width/2;
+                    bound.x = start as i64;
+                }
+                layout::alignment::HorizontalAlignment::Right => {
+                    let right = max_width;
+                    let start = right - bound.width;
+                    bound.x = start as i64;
+                }
+            }
 
-                let mut greedy_count = 0;
-                let mut expandable_count = 0;
-                let mut static_width = spacing_sizing;
+            // move from 0 based bounds to the actual frame of the container
+            bound.x += bounds.x;
+            bound.y += bounds.y;
 
-                for node in &nodes {
-                    match node.sizing.horizontal {
-                        Sizing::Static(n) => static_width += n,
-                        Sizing::Greedy(_) => {
-                            expandable_count += 1;
-                            greedy_count += 1;
-                        }
-                        Sizing::Flexible(_) => {
-                            expandable_count += 1;
-                        }
-                    }
+            bound
+        }).collect();
+
+        nodes.into_iter().zip(final_bounds).collect()
+    }
+
+    /// Where each item of a horizontal stack is drawn, when the stack is drawn in `bounds`.
+    fn horizontal_stack_frames<Content: Clone + Default + Debug>(
+        alignment: &layout::alignment::VerticalAlignment,
+        spacing: usize,
+        nodes: Vec<SizedNode<Content>>,
+        bounds: &Rect,
+    ) -> Vec<(SizedNode<Content>, Rect)> {
+        let mut max_height = 0usize;
+
+        let spacing_sizing = spacing * (nodes.len().saturating_sub(1));
+
+        let mut last_bounds = Rect::zero();
+
+        let mut greedy_count = 0;
+        let mut expandable_count = 0;
+        let mut static_width = spacing_sizing;
+
+        for node in &nodes {
+            match node.sizing.horizontal {
+                Sizing::Static(n) => static_width += n,
+                Sizing::Greedy(_) => {
+                    expandable_count += 1;
+                    greedy_count += 1;
+                }
+                Sizing::Flexible(_) => {
+                    expandable_count += 1;
                 }
+            }
+        }
 
-                let mut greedy_space = bounds.width.saturating_sub(static_width);
-                let greedy_size = if greedy_count == 0 {
-                    if expandable_count == 0 {
-                        0
-                    } else {
-                        greedy_space / expandable_count
+        let mut greedy_space = bounds.width.saturating_sub(static_width);
+        let greedy_size = greedy_space
+            .checked_div(greedy_count)
+            .or_else(|| greedy_space.checked_div(expandable_count))
+            .unwrap_or(0);
+
+        let mut new_nodes = vec![];
+
+        for node in &nodes {
+            let mut n = node.clone();
+            n.sizing.horizontal = match n.sizing.horizontal {
+                Sizing::Static(sz) => Sizing::Static(sz),
+                Sizing::Flexible(sz) if greedy_count != 0 => {
+                    // When there's some greedy node, they take priority. Which means the flexible item
+                    // cannot grow.
+                    // TODO: When greedy sizing gets a max value implemented, this will need to change.
+                    Sizing::Static(sz)
+                }
+                Sizing::Greedy(tight) | Sizing::Flexible(tight) => {
+                    greedy_space -= greedy_size;
+                    let mut node_width = greedy_size;
+                    if greedy_space < greedy_size {
+                        node_width += greedy_space;
+                        greedy_space = 0;
                     }
-                } else {
-                    greedy_space / greedy_count
-                };
 
-                let mut new_nodes = vec![];
-
-                for node in &nodes {
-                    let mut n = node.clone();
-                    n.sizing.horizontal = match n.sizing.horizontal {
-                        Sizing::Static(sz) => Sizing::Static(sz),
-                        Sizing::Flexible(sz) if greedy_count != 0 => {
-                            // When there's some greedy node, they take priority. Which means the flexible item
-                            // cannot grow.
-                            // TODO: When greedy sizing gets a max value implemented, this will need to change.
-                            Sizing::Static(sz)
-                        }
-                        Sizing::Greedy(tight) | Sizing::Flexible(tight) => {
-                            greedy_space -= greedy_size;
-                            let mut node_width = greedy_size;
-                            if greedy_space < greedy_size {
-                                node_width += greedy_space;
-                                greedy_space = 0;
-                            }
+                    Sizing::Static(node_width.max(tight))
+                }
+            };
 
-                            Sizing::Static(node_width.max(tight))
-                        }
-                    };
+            new_nodes.push(n);
+        }
 
-                    new_nodes.push(n);
-                }
+        let nodes = new_nodes;
 
-                let nodes = new_nodes;
+        let mut raw_bounds = vec![];
+        for node in &nodes {
+            let size = node.sizing.fit_into(bounds);
 
-                let mut raw_bounds = vec![];
-                for node in &nodes {
-                    let size = node.sizing.fit_into(bounds);
+            let spacing_offset = if raw_bounds.is_empty() {
+                0
+            } else {
+                spacing as i64
+            };
 
-                    let spacing_offset = if raw_bounds.is_empty() {
-                        0
-                    } else {
-                        spacing as i64
-                    };
+            let node_bounds = Rect::new(
+                last_bounds.max_x() + spacing_offset,
+                0,
+                size.width,
+                size.height,
+            );
+            last_bounds = node_bounds.clone();
 
-                    let node_bounds = Rect::new(
-                        last_bounds.max_x() + spacing_offset,
-                        0,
-                        size.width,
-                        size.height,
-                    );
-                    last_bounds = node_bounds.clone();
+            if node_bounds.height > max_height {
+                max_height = node_bounds.height;
+            }
 
-                    if node_bounds.height > max_height {
-                        max_height = node_bounds.height;
-                    }
+            raw_bounds.push(node_bounds);
+        }
 
-                    raw_bounds.push(node_bounds);
+        let baseline_offsets = match baseline_edge(alignment) {
+            Some(last) => {
+                let items: Vec<_> = nodes.iter().zip(raw_bounds.iter().cloned()).collect();
+
+                baseline_offsets(&items, last)
+            }
+            None => vec![]
+        };
+
+        let final_bounds: Vec<_> = raw_bounds.into_iter().enumerate().map(|(i, mut bound)| {
+            match alignment {
+                layout::alignment::VerticalAlignment::Top => { /* Already aligned to the top */}
+                layout::alignment::VerticalAlignment::Center => {
+                    let center = max_height / 2;
+                    let start = center - bound.height/2;
+                    bound.y = start as i64;
                 }
+                layout::alignment::VerticalAlignment::Bottom => {
+                    let bottom = max_height;
+                    let start = bottom - bound.height;
+                    bound.y = start as i64;
+                }
+                layout::alignment::VerticalAlignment::FirstBaseline
+                | layout::alignment::VerticalAlignment::LastBaseline => {
+                    bound.y = baseline_offsets[i] as i64;
+                }
+            }
 
-                let final_bounds: Vec<_> = raw_bounds.into_iter().map(|mut bound| {
-                    match &alignment {
-                        layout::alignment::VerticalAlignment::Top => { /* Already aligned to the top */}
-                        layout::alignment::VerticalAlignment::Center => {
-                            let center = max_height / 2;
-                            let start = center - bound.height/2;
-                            bound.y = start as i64;
-                        }
-                        layout::alignment::VerticalAlignment::Bottom => {
-                            let bottom = max_height;
-                            let start = bottom - bound.height;
-                            bound.y = start as i64;
-                        }
-                    }
+            // move from 0 based bounds to the actual frame of the container
+            bound.x += bounds.x;
+            bound.y += bounds.y;
 
-                    // move from 0 based bounds to the actual frame of the container
-                    bound.x += bounds.x;
-                    bound.y += bounds.y;
+            bound
+        }).collect();
 
-                    bound
-                }).collect();
+        nodes.into_iter().zip(final_bounds).collect()
+    }
 
-                nodes
-                    .into_iter()
-                    .enumerate()
-                    .flat_map(|(i, node)| {
-                        let size = &final_bounds[i];
+    /// Where each item of a normal stack is drawn, when the stack is drawn in `bounds`.
+    fn normal_stack_frames<Content: Clone + Default + Debug>(
+        alignment: &layout::alignment::Alignment,
+        nodes: Vec<SizedNode<Content>>,
+        bounds: &Rect,
+    ) -> Vec<(SizedNode<Content>, Rect)> {
+        let mut max_height = 0usize;
+        let mut max_width = 0usize;
+
+        let mut raw_bounds = vec![];
+        for node in &nodes {
+            let size = node.sizing.fit_into(bounds);
+
+            let node_bounds = Rect::new(
+                0,
+                0,
+                size.width,
+                size.height,
+            );
+
+            if node_bounds.height > max_height {
+                max_height = node_bounds.height;
+            }
 
-                        Self::resolve_draw_commands(&node, size)
-                    })
-                    .collect::<Vec<_>>()
+            if node_bounds.width > max_width {
+                max_width = node_bounds.width;
             }
-            NormalStack(alignment, nodes) => {
-                let mut max_height = 0usize;
-                let mut max_width = 0usize;
-
-                let mut raw_bounds = vec![];
-                for node in &nodes {
-                    let size = node.sizing.fit_into(bounds);
-
-                    let node_bounds = Rect::new(
-                        0,
-                        0,
-                        size.width,
-                        size.height,
-                    );
-
-                    if node_bounds.height > max_height {
-                        max_height = node_bounds.height;
-                    }
 
-                    if node_bounds.width > max_width {
-                        max_width = node_bounds.width;
-                    }
+            raw_bounds.push(node_bounds);
+        }
 
-                    raw_bounds.push(node_bounds);
+        let final_bounds: Vec<_> = raw_bounds.into_iter().map(|mut bound| {
+            match &alignment.vertical() {
+                // TODO: Line up baselines of overlapping items. Aligned to the top for now.
+                layout::alignment::VerticalAlignment::Top
+                | layout::alignment::VerticalAlignment::FirstBaseline
+                | layout::alignment::VerticalAlignment::LastBaseline => { /* Already aligned to the top */}
+                layout::alignment::VerticalAlignment::Center => {
+                    let center = max_height / 2;
+                    let start = center - bound.height/2;
+                    bound.y = start as i64;
+                }
+                layout::alignment::VerticalAlignment::Bottom => {
+                    let bottom = max_height;
+                    let start = bottom - bound.height;
+                    bound.y = start as i64;
                 }
+            }
 
-                let final_bounds: Vec<_> = raw_bounds.into_iter().map(|mut bound| {
-                    match &alignment.vertical() {
-                        layout::alignment::VerticalAlignment::Top => { /* Already aligned to the top */}
-                        layout::alignment::VerticalAlignment::Center => {
-                            let center = max_height / 2;
-                            let start = center - bound.height/2;
-                            bound.y = start as i64;
-                        }
-                        layout::alignment::VerticalAlignment::Bottom => {
-                            let bottom = max_height;
-                            let start = bottom - bound.height;
-                            bound.y = start as i64;
-                        }
-                    }
+            match &alignment.horizontal() {
+                layout::alignment::HorizontalAlignment::Left => { /* Already aligned to the left */}
+                layout::alignment::HorizontalAlignment::Center => {
+                    let center = max_width / 2;
+                    let start = center - bound.width/2;
+                    bound.x = start as i64;
+                }
+                layout::alignment::HorizontalAlignment::Right => {
+                    let right = max_width;
+                    let start = right - bound.width;
+                    bound.x = start as i64;
+                }
+            }
 
-                    match &alignment.horizontal() {
-                        layout::alignment::HorizontalAlignment::Left => { /* Already aligned to the left */}
-                        layout::alignment::HorizontalAlignment::Center => {
-                            let center = max_width / 2;
-                            let start = center - bound.width/2;
-                            bound.x = start as i64;
-                        }
-                        layout::alignment::HorizontalAlignment::Right => {
-                            let right = max_width;
-                            let start = right - bound.width;
-                            bound.x = start as i64;
-                        }
-                    }
+            // move from 0 based bounds to the actual frame of the container
+            bound.x += bounds.x;
+            bound.y += bounds.y;
+
+            bound
+        }).collect();
+
+        nodes.into_iter().zip(final_bounds).collect()
+    }
+
+    /// Distance from the top of `bounds` to the baseline of the first (or `last`) line of text in the node,
+    /// when it's drawn in those bounds. `None` when it has no text.
+    /// Vertical stacks take it from their first (or last) item with text, and the other stacks from
+    /// the highest first baseline (or lowest last one) of their items.
+    pub fn resolve_baseline<Content: Clone + Default + Debug>(
+        sized_node: &SizedNode<Content>,
+        bounds: &Rect,
+        last: bool,
+    ) -> Option<usize> {
+        use SizedItem::*;
 
-                    // move from 0 based bounds to the actual frame of the container
-                    bound.x += bounds.x;
-                    bound.y += bounds.y;
+        let pick = |text: ResolvedText<'_, '_>| if last { text.last_baseline() } else { text.first_baseline() };
 
-                    bound
-                }).collect();
+        let in_frame = |node: &SizedNode<Content>, frame: &Rect| {
+            let baseline = Self::resolve_baseline(node, frame, last)? as i64 + frame.y - bounds.y;
 
-                nodes
-                    .into_iter()
-                    .enumerate()
-                    .flat_map(|(i, node)| {
-                        let size = &final_bounds[i];
+            Some(baseline.max(0) as usize)
+        };
 
-                        Self::resolve_draw_commands(&node, size)
-                    })
-                    .collect::<Vec<_>>()
+        let shrunk = |width: usize, height: usize| {
+            let mut shrunk_bounds = bounds.clone();
+            shrunk_bounds.width = shrunk_bounds.width.saturating_sub(width);
+            shrunk_bounds.height = shrunk_bounds.height.saturating_sub(height);
+
+            shrunk_bounds
+        };
+
+        match sized_node.node.as_ref() {
+            Text(text, _, font, style) => {
+                pick(font.calculate_lines(text, &text_bounds(bounds, &style.extra_size(font))))
             }
-            Detached(wrapped_content, alignment, behavior, node) => {
-                // FIXME: Alignment not handled
-                let mut result: Vec<DrawCommand<Content>> = vec![];
-                
-                let wrapped_bounds = wrapped_content.sizing.fit_into(bounds);
-                let mut content_bounds = node.sizing.fit_into(&wrapped_bounds);
+            RichText(text) => pick(text.calculate_lines(&text_bounds(bounds, &text.extra_size()))),
+            Width(_, node) | Height(_, node) | VTopAlign(node) | HLeftAlign(node) | HCenter(node) | HRightAlign(node)
+            | RightPadding(_, node) | Background(_, node) | GradientBackground(_, node) | Effect(_, node)
+            | Detached(node, _, _, _) => {
+                in_frame(node, &node.sizing.fit_into(bounds))
+            }
+            LeftPadding(n, node) => in_frame(node, &node.sizing.fit_into(&shrunk(*n, 0))),
+            BottomPadding(n, node) => in_frame(node, &node.sizing.fit_into(&shrunk(0, *n))),
+            TopPadding(n, node) => {
+                let mut frame = node.sizing.fit_into(&shrunk(0, *n));
+                frame.y += *n as i64;
 
-                match &alignment.horizontal() {
-                    layout::alignment::HorizontalAlignment::Left => { /* Already aligned to the left */}
-                    layout::alignment::HorizontalAlignment::Center => {
-                        let center = wrapped_bounds.width / 2;
-                        let start = center - content_bounds.width/2;
-                        content_bounds.x += start as i64;
-                    }
-                    layout::alignment::HorizontalAlignment::Right => {
-                        let right = wrapped_bounds.width;
-                        let start = right - content_bounds.width;
-                        content_bounds.x += start as i64;
-                    }
+                in_frame(node, &frame)
+            }
+            VCenter(node) => {
+                let mut frame = node.sizing.fit_into(bounds);
+                frame.y += (bounds.height / 2) as i64 - (frame.height / 2) as i64;
+
+                in_frame(node, &frame)
+            }
+            VBottomAlign(node) => {
+                let mut frame = node.sizing.fit_into(bounds);
+                frame.y += bounds.height as i64 - frame.height as i64;
+
+                in_frame(node, &frame)
+            }
+            Border(n, _, edges, node) => {
+                let horizontal_edges = edges.iter().filter(|edge| matches!(edge, Edge::Left | Edge::Right)).count();
+                let vertical_edges = edges.iter().filter(|edge| matches!(edge, Edge::Top | Edge::Bottom)).count();
+
+                let mut frame = node.sizing.fit_into(&shrunk(n * horizontal_edges, n * vertical_edges));
+                if edges.contains(&Edge::Top) {
+                    frame.y += *n as i64;
                 }
 
-                match &alignment.vertical() {
-                    layout::alignment::VerticalAlignment::Top => { /* Already aligned to the left */}
-                    layout::alignment::VerticalAlignment::Center => {
-                        let center = wrapped_bounds.height / 2;
-                        let start = center - content_bounds.height/2;
-                        content_bounds.y += start as i64;
-                    }
-                    layout::alignment::VerticalAlignment::Bottom => {
-                        let bottom = wrapped_bounds.height;
-                        let start = bottom - content_bounds.height;
-                        content_bounds.y += start as i64;
+                in_frame(node, &frame)
+            }
+            VerticalStack(alignment, spacing, nodes) => {
+                let frames = Self::vertical_stack_frames(alignment, *spacing, nodes.clone(), bounds);
+                let mut baselines = frames.iter().filter_map(|(node, frame)| in_frame(node, frame));
+
+                if last { baselines.next_back() } else { baselines.next() }
+            }
+            HorizontalStack(alignment, spacing, nodes) => {
+                let frames = Self::horizontal_stack_frames(alignment, *spacing, nodes.clone(), bounds);
+                let baselines = frames.iter().filter_map(|(node, frame)| in_frame(node, frame));
+
+                if last { baselines.max() } else { baselines.min() }
+            }
+            NormalStack(alignment, nodes) => {
+                let frames = Self::normal_stack_frames(alignment, nodes.clone(), bounds);
+                let baselines = frames.iter().filter_map(|(node, frame)| in_frame(node, frame));
+
+                if last { baselines.max() } else { baselines.min() }
+            }
+            Shape(..) | GradientShape(..) | Image(..) | Divider(..) => None
+        }
+    }
+
+    /// Draws the glyphs and decorations of resolved text, taking the font, content and style of each glyph
+    /// from the span it belongs to. Shadows are drawn first, so they stay under every span.
+    /// Text is drawn as one `TextLine` per run of glyphs from the same span, and split at tabs,
+    /// so each run can be laid out again on its own the same way.
+    fn resolve_text_commands<Content: Clone + Default + Debug>(
+        text: &str,
+        resolved_text: &ResolvedText<'_, '_>,
+        spans: &[(&Arc<Font>, &Content, &TextStyle<Content>)],
+        bounds: &Rect,
+    ) -> Vec<DrawCommand<Content>> {
+        let mut commands = vec![];
+
+        let shadow_offset = TextStyle::<Content>::SHADOW_OFFSET as i64;
+
+        for is_shadow in [true, false] {
+            for line in resolved_text.lines() {
+                let line_bounds = Rect::new(
+                    bounds.x + line.bounds().x, // FIXME: Left align only at the moment
+                    bounds.y + line.bounds().y,
+                    line.bounds().size().width,
+                    line.bounds().size().height,
+                );
+
+                // The shadow pass draws everything of the spans that have a shadow with the shadow's content.
+                let layer = |span: usize, content: Option<&Content>| -> Option<(i64, Content)> {
+                    let (_, _, style) = spans[span];
+
+                    if is_shadow {
+                        content.and(style.shadow.clone()).map(|shadow| (shadow_offset, shadow))
+                    } else {
+                        content.cloned().map(|content| (0, content))
                     }
+                };
+
+                let text_runs = line.glyphs.chunk_by(|a, b| {
+                    a.span() == b.span() && !text[a.index()..b.index()].contains('\t')
+                });
+
+                for run in text_runs {
+                    let (Some(first), Some(last)) = (run.first(), run.last()) else { continue };
+                    let (font, content, style) = spans[first.span()];
+                    let Some((layer_offset, content)) = layer(first.span(), Some(content)) else { continue };
+
+                    // Shadows are a single color, whatever the colors of the glyphs.
+                    let palette = if is_shadow { vec![] } else { style.palette.clone() };
+
+                    let last_char_len = text[last.index()..].chars().next().map(char::len_utf8).unwrap_or(0);
+                    let run_text = &text[first.index()..last.index() + last_char_len];
+
+                    // Glyphs taller than the rest of the run (from fallbacks) start higher up.
+                    let top = run.iter().map(|glyph| glyph.offset().height).min().unwrap_or(0);
+                    let bottom = run.iter().map(|glyph| glyph.offset().height + glyph.size().height).max().unwrap_or(0);
+
+                    commands.push(DrawCommand::TextLine(
+                        Rect::new(
+                            line_bounds.x + first.offset().width as i64 + layer_offset,
+                            line_bounds.y + top as i64 + layer_offset,
+                            last.offset().width + last.size().width - first.offset().width,
+                            bottom - top
+                        ),
+                        run_text.to_string(),
+                        font.clone(),
+                        content,
+                        palette
+                    ));
                 }
 
-                let mut detached_commands = Self::resolve_draw_commands(&node, &content_bounds);
-                let mut wrapped_commands = Self::resolve_draw_commands(&wrapped_content, &wrapped_bounds);
+                // Decorations go under each run of consecutive glyphs from the same span.
+                let runs = line.glyphs.chunk_by(|a, b| a.span() == b.span());
 
-                match behavior {
-                    DetachedBehavior::Background => {
-                        result.append(&mut detached_commands);
-                        result.append(&mut wrapped_commands);
-                    }
-                    DetachedBehavior::Overlay => {
-                        result.append(&mut wrapped_commands);
-                        result.append(&mut detached_commands);
+                for run in runs {
+                    let (Some(first), Some(last)) = (run.first(), run.last()) else { continue };
+                    let (font, _, style) = spans[first.span()];
+
+                    let run_x = line_bounds.x + first.offset().width as i64;
+                    let run_width = last.offset().width + last.size().width - first.offset().width;
+                    // Glyphs sit on the baseline of the line, and so do the font's metrics.
+                    let font_y = line_bounds.y + line.baseline() as i64 - font.ascent() as i64;
+
+                    let decorations = [
+                        (style.underline.as_ref(), font.underline_position()),
+                        (style.strikethrough.as_ref(), font.strikethrough_position())
+                    ];
+
+                    for (decoration, position) in decorations {
+                        let Some((layer_offset, decoration)) = layer(first.span(), decoration) else { continue };
+
+                        commands.push(DrawCommand::FillRect(
+                            Rect::new(
+                                run_x + layer_offset,
+                                font_y + position as i64 + layer_offset,
+                                run_width,
+                                font.decoration_thickness()
+                            ),
+                            decoration
+                        ));
                     }
                 }
+            }
+        }
 
-                result
+        commands
+    }
+}
+
+/// The pixels of `canvas` drawn in a frame of `size` with `mode`, scaled by nearest neighbor.
+/// The parts of the frame the image doesn't cover are empty.
+fn image_bitmap<Content: Clone + Default>(canvas: &Canvas<Content>, mode: ContentMode, size: &Size) -> Vec<Option<Content>> {
+    let image = &canvas.size;
+
+    if image.width == 0 || image.height == 0 {
+        return vec![None; size.width * size.height];
+    }
+
+    let scale_x = size.width as f64 / image.width as f64;
+    let scale_y = size.height as f64 / image.height as f64;
+
+    let scaled = |scale: f64| Size::new(
+        (image.width as f64 * scale).round() as usize,
+        (image.height as f64 * scale).round() as usize
+    );
+
+    let scaled_size = match mode {
+        ContentMode::Fit => scaled(scale_x.min(scale_y)),
+        ContentMode::Fill => scaled(scale_x.max(scale_y)),
+        ContentMode::Stretch => size.clone(),
+        ContentMode::Center => image.clone()
+    };
+
+    // Where the scaled image starts in the frame, negative when it's cut.
+    let start_x = (size.width as i64 - scaled_size.width as i64) / 2;
+    let start_y = (size.height as i64 - scaled_size.height as i64) / 2;
+
+    let mut bitmap = Vec::with_capacity(size.width * size.height);
+
+    for y in 0..size.height as i64 {
+        for x in 0..size.width as i64 {
+            let (scaled_x, scaled_y) = (x - start_x, y - start_y);
+
+            if scaled_x < 0 || scaled_y < 0 || scaled_x >= scaled_size.width as i64 || scaled_y >= scaled_size.height as i64 {
+                bitmap.push(None);
+                continue;
             }
-            Shape(shape, shape_behavior, content) => match shape_behavior {
-                ShapeBehavior::Fill => vec![DrawCommand::FillShape(bounds.clone(), shape, content)],
-                ShapeBehavior::Stroke(n) => {
-                    vec![DrawCommand::StrokeShape(bounds.clone(), n, shape, content)]
-                }
-            },
+
+            let image_x = scaled_x as usize * image.width / scaled_size.width;
+            let image_y = scaled_y as usize * image.height / scaled_size.height;
+
+            bitmap.push(canvas.get_at(image_x, image_y).cloned());
         }
     }
+
+    bitmap
+}
+
+/// The bounds left for the glyphs of a text, once the space its style needs is taken out.
+fn text_bounds(bounds: &Rect, extra_size: &Size) -> Rect {
+    let mut text_bounds = bounds.clone();
+    text_bounds.width = text_bounds.width.saturating_sub(extra_size.width);
+    text_bounds.height = text_bounds.height.saturating_sub(extra_size.height);
+
+    text_bounds
+}
+
+/// For baseline alignments, whether it's the last line of text that gets lined up.
+fn baseline_edge(alignment: &layout::alignment::VerticalAlignment) -> Option<bool> {
+    match alignment {
+        layout::alignment::VerticalAlignment::FirstBaseline => Some(false),
+        layout::alignment::VerticalAlignment::LastBaseline => Some(true),
+        _ => None
+    }
+}
+
+/// How far down each item (drawn in its bounds) has to move for all of their baselines to line up.
+/// Items without text line up their bottom edge.
+fn baseline_offsets<Content: Clone + Default + Debug>(items: &[(&SizedNode<Content>, Rect)], last: bool) -> Vec<usize> {
+    let baselines: Vec<_> = items
+        .iter()
+        .map(|(node, bounds)| SizeResolver::resolve_baseline(node, bounds, last).unwrap_or(bounds.height))
+        .collect();
+
+    let lowest_baseline = baselines.iter().max().copied().unwrap_or(0);
+
+    baselines
+        .into_iter()
+        .map(|baseline| lowest_baseline - baseline)
+        .collect()
+}
+
+#[test]
+fn lines_up_baselines_in_horizontal_stack() {
+    use crate::fonts::Font;
+
+    let low_font = Font::four_by_five().as_ref().clone().with_descent(2);
+
+    let node: Node<u8, ()> = Node::HorizontalStack(
+        layout::alignment::VerticalAlignment::FirstBaseline,
+        1,
+        vec![
+            Node::text("a", 1).padding_top(1),
+            Node::text_with_font("b", 1, Font::four_by_five()),
+            Node::text_with_font("c", 1, low_font),
+        ]
+    );
+
+    let bounds = Rect::sized(100, 100);
+    let sized_node = SizeCalculator::resolve_size(&node, &bounds, &mut ());
+
+    // The 4x5 glyph going 2 rows under the baseline makes the stack taller than any of its items.
+    assert_eq!(sized_node.sizing.vertical.min_content_size(), 7);
+
+    let glyph_tops: Vec<_> = SizeResolver::resolve_draw_commands(&sized_node, &bounds)
+        .into_iter()
+        .filter_map(|command| match command {
+            DrawCommand::TextLine(rect, ..) => Some(rect.y),
+            _ => None
+        })
+        .collect();
+
+    assert_eq!(glyph_tops, vec![2, 0, 2]);
+}
+
+#[test]
+fn emits_text_lines_per_run() {
+    use crate::fonts::{rich_text::RichText, Font};
+
+    let node: Node<u8, ()> = Node::RichText(
+        RichText::new()
+            .span("ab cd", 1, Font::three_by_three())
+            .span("ef\tg", 2, Font::four_by_five())
+    );
+
+    let bounds = Rect::sized(100, 100);
+    let sized_node = SizeCalculator::resolve_size(&node, &bounds, &mut ());
+
+    let text_lines: Vec<_> = SizeResolver::resolve_draw_commands(&sized_node, &bounds)
+        .into_iter()
+        .filter_map(|command| match command {
+            DrawCommand::TextLine(rect, text, font, content, _) => Some((rect, text, font.name.clone(), content)),
+            _ => None
+        })
+        .collect();
+
+    // Spaces stay in the run, tabs split it; the 3x3 glyphs sit on the baseline of the taller 4x5 ones.
+    assert_eq!(text_lines, vec![
+        (Rect::new(0, 2, 19, 3), "ab cd".to_string(), "3x3Mono".to_string(), 1),
+        (Rect::new(20, 0, 9, 5), "ef".to_string(), "4x5Mono".to_string(), 2),
+        (Rect::new(48, 0, 4, 5), "g".to_string(), "4x5Mono".to_string(), 2),
+    ]);
+}
+
+#[test]
+fn scales_images_by_content_mode() {
+    use crate::graphics::canvas::Canvas;
+
+    // A 2x1 image: 1 on the left, 2 on the right.
+    let image: Arc<Canvas<u8>> = Arc::new(Canvas::create_with_content(vec![1, 2], &Size::new(2, 1)));
+
+    let draw = |mode: ContentMode, width: usize, height: usize| -> (Rect, Vec<Option<u8>>) {
+        let node: Node<u8, ()> = Node::image(image.clone(), mode).width(width).height(height);
+        let bounds = Rect::sized(10, 10);
+
+        let sized_node = SizeCalculator::resolve_size(&node, &bounds, &mut ());
+        let commands = SizeResolver::resolve_draw_commands(&sized_node, &sized_node.sizing.fit_into(&bounds));
+
+        match commands.into_iter().next() {
+            Some(DrawCommand::Bitmap(bitmap, rect)) => (rect, bitmap),
+            _ => panic!("Images are drawn as bitmaps")
+        }
+    };
+
+    let (o, a, b) = (None, Some(1), Some(2));
+
+    assert_eq!(draw(ContentMode::Stretch, 4, 2), (Rect::sized(4, 2), vec![a, a, b, b, a, a, b, b]));
+    assert_eq!(draw(ContentMode::Fit, 4, 4), (Rect::sized(4, 4), vec![
+        o, o, o, o,
+        a, a, b, b,
+        a, a, b, b,
+        o, o, o, o,
+    ]));
+    assert_eq!(draw(ContentMode::Fill, 2, 2), (Rect::sized(2, 2), vec![a, b, a, b]));
+    assert_eq!(draw(ContentMode::Center, 4, 3), (Rect::sized(2, 1), vec![a, b]));
+
+    // Without room for its own size, a centered image is cut.
+    assert_eq!(draw(ContentMode::Center, 1, 1), (Rect::sized(1, 1), vec![a]));
+}
+
+#[test]
+fn stretches_dividers_across_stacks() {
+    let fills = |node: Node<u8, ()>| -> Vec<Rect> {
+        let bounds = Rect::sized(20, 20);
+
+        let sized_node = SizeCalculator::resolve_size(&node, &bounds, &mut ());
+        let commands = SizeResolver::resolve_draw_commands(&sized_node, &sized_node.sizing.fit_into(&bounds));
+
+        commands
+            .into_iter()
+            .filter_map(|command| match command {
+                DrawCommand::FillRect(rect, 2) => Some(rect),
+                _ => None
+            })
+            .collect()
+    };
+
+    let row = || Node::Shape(layout::geometry::Shape::Rectangle, ShapeBehavior::Fill, 1).width(6).height(3);
+
+    let column = Node::VerticalStack(layout::alignment::HorizontalAlignment::Left, 1, vec![
+        row(),
+        Node::divider(2),
+        row().width(8),
+    ]);
+    // Across the whole stack, and only as tall as it is thick.
+    assert_eq!(fills(column), vec![Rect::new(0, 4, 20, 1)]);
+
+    let line = Node::horizontal_stack(vec![row(), Node::divider_with_thickness(2, 2), row().height(5)]);
+    assert_eq!(fills(line), vec![Rect::new(6, 0, 2, 20)]);
+
+    // Padding around a divider keeps it as thick, and still going across the stack.
+    let padded = Node::VerticalStack(layout::alignment::HorizontalAlignment::Left, 0, vec![
+        row(),
+        Node::divider(2).padding_vertical(2).padding_horizontal(3),
+        row(),
+    ]);
+    assert_eq!(fills(padded), vec![Rect::new(3, 5, 14, 1)]);
+
+    let padded_line = Node::horizontal_stack(vec![row(), Node::divider(2).padding_vertical(4), row()]);
+    assert_eq!(fills(padded_line), vec![Rect::new(6, 4, 1, 12)]);
+}
+
+#[test]
+fn lines_up_baselines_of_nested_stacks() {
+    use crate::fonts::Font;
+
+    let labels = |alignment| -> Node<u8, ()> {
+        Node::HorizontalStack(alignment, 1, vec![
+            Node::text_with_font("a", 1, Font::four_by_five()),
+            // Two labels, one under the other.
+            Node::VerticalStack(layout::alignment::HorizontalAlignment::Left, 0, vec![
+                Node::text_with_font("b", 1, Font::four_by_five()),
+                Node::text_with_font("c", 1, Font::four_by_five()),
+            ]),
+        ])
+    };
+
+    let glyph_tops = |node: Node<u8, ()>| -> Vec<i64> {
+        let bounds = Rect::sized(100, 100);
+        let sized_node = SizeCalculator::resolve_size(&node, &bounds, &mut ());
+
+        SizeResolver::resolve_draw_commands(&sized_node, &bounds)
+            .into_iter()
+            .filter_map(|command| match command {
+                DrawCommand::TextLine(rect, ..) => Some(rect.y),
+                _ => None
+            })
+            .collect()
+    };
+
+    // In line with the first label of the nested stack, and with the last one.
+    assert_eq!(glyph_tops(labels(layout::alignment::VerticalAlignment::FirstBaseline)), vec![0, 0, 5]);
+    assert_eq!(glyph_tops(labels(layout::alignment::VerticalAlignment::LastBaseline)), vec![5, 0, 5]);
 }
@@ -1,11 +1,12 @@
-use std::{collections::HashSet, fmt::Debug};
-use crate::fonts::Font;
+use std::{collections::HashSet, fmt::Debug, sync::Arc};
+use crate::{fonts::{rich_text::RichText, style::TextStyle, Font}, graphics::{canvas::Canvas, gradient::Gradient}, rendering::effects::Effect};
 
-use super::{alignment, geometry::Shape, node::{DetachedBehavior, ShapeBehavior}, sizing};
+use super::{alignment, geometry::Shape, node::{ContentMode, DetachedBehavior, ShapeBehavior}, sizing};
 
 #[derive(Clone, Debug)]
 pub enum SizedItem<Content: Clone + Default + Debug> {
-    Text(String, Content, &'static Font),
+    Text(String, Content, Arc<Font>, TextStyle<Content>),
+    RichText(RichText<Content>),
     Width(usize, SizedNode<Content>),
     Height(usize, SizedNode<Content>),
     TopPadding(usize, SizedNode<Content>),
@@ -19,6 +20,8 @@ pub enum SizedItem<Content: Clone + Default + Debug> {
     VTopAlign(SizedNode<Content>),
     HLeftAlign(SizedNode<Content>),
     Background(Content, SizedNode<Content>),
+    GradientBackground(Gradient<Content>, SizedNode<Content>),
+    Effect(Effect<Content>, SizedNode<Content>),
     Detached(SizedNode<Content>, alignment::Alignment, DetachedBehavior, SizedNode<Content>),
     Border(usize, Content, HashSet<alignment::Edge>, SizedNode<Content>),
 
@@ -26,7 +29,11 @@ pub enum SizedItem<Content: Clone + Default + Debug> {
     HorizontalStack(alignment::VerticalAlignment, usize, Vec<SizedNode<Content>>),
     NormalStack(alignment::Alignment, Vec<SizedNode<Content>>),
 
-    Shape(Shape, ShapeBehavior, Content)
+    Shape(Shape, ShapeBehavior, Content),
+    GradientShape(Shape, Gradient<Content>),
+    Image(Arc<Canvas<Content>>, ContentMode),
+    /// Fills its frame, which was sized across the stack it's in.
+    Divider(Content)
 }
 
 #[derive(Clone, Debug)]
@@ -4,7 +4,7 @@ use crate::layout::alignment::VerticalAlignment;
 use crate::output::ppm_output::PpmOutput;
 use crate::{
     fonts::Font,
-    graphics::{canvas::Canvas, pixel::TwoBitPixel},
+    graphics::{canvas::Canvas, pixel::{Blend, TwoBitPixel}},
     layout::{
         alignment::HorizontalAlignment,
         size_resolution::{SizeCalculator, SizeResolver},
@@ -69,7 +69,7 @@ fn horizontal_stack() {
     ));
 }
 
-fn draw_layout<Content: Debug + Default + Clone>(name: &str, node: Node<Content, ()>) -> String
+fn draw_layout<Content: Debug + Default + Clone + Blend>(name: &str, node: Node<Content, ()>) -> String
 where
     Canvas<Content>: PpmOutput,
 {
@@ -100,7 +100,7 @@ fn write_example_output(name: &str, contents: &String) {
 }
 
 fn dummy_component<Content: Debug + Default + Clone>() -> Node<Content, ()> {
-    Node::Text("".to_string(), Font::three_by_three(), Content::default())
+    Node::Text("".to_string(), Font::three_by_three(), Content::default(), Default::default())
         .height(1)
         .width(1)
 }
@@ -11,4 +11,5 @@ pub mod graphics;
 // pub mod animation;
 pub mod rendering;
 pub mod output;
+pub mod input;
 pub mod fonts;
\ No newline at end of file
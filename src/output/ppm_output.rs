@@ -1,6 +1,6 @@
 use crate::graphics::{
     canvas::Canvas,
-    pixel::{RGBPixel, TwoBitPixel},
+    pixel::{RGBAPixel, RGBPixel, TwoBitPixel},
 };
 
 pub trait ImageSource {
@@ -100,6 +100,33 @@ impl PpmOutput for Canvas<RGBPixel> {
     }
 }
 
+/// PPM has no transparency, so the pixels are flattened over the white of the palette.
+/// Use `Canvas::flatten_over` first for any other background.
+impl PpmOutput for Canvas<RGBAPixel> {
+    fn header(&self) -> String {
+        format!("P3\n{} {}\n{}", self.width(), self.height(), u8::MAX)
+    }
+
+    fn pixel_row(&self, y: usize) -> String {
+        let mut output = String::new();
+        let background = RGBPixel::white();
+
+        for x in 0..self.width() {
+            let pixel = self.get_at(x, y).cloned().unwrap_or_default().flatten(&background);
+
+            let r = pixel.r();
+            let g = pixel.g();
+            let b = pixel.b();
+
+            output.push_str(&format!("{r} {g} {b}"));
+
+            output.push_str("   ");
+        }
+
+        output
+    }
+}
+
 impl Canvas<RGBPixel> {
     fn max_value(&self) -> usize {
         255
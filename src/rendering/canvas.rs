@@ -1,15 +1,14 @@
-use std::u8;
-
-use super::DrawCommand;
+use super::{effects::Effect, offscreen::Offscreen, DrawCommand};
 use crate::{
-    graphics::canvas::Canvas,
+    fonts::Font,
+    graphics::{canvas::Canvas, gradient::Gradient, pixel::Blend},
     layout::geometry::{CornerRadius, Rect, Shape, Size},
 };
 
 use tiny_skia;
 
 pub trait Drawable {
-    type Content: Clone + Default;
+    type Content: Clone + Default + Blend;
     fn fill_rect(&mut self, bounds: &Rect, content: &Self::Content);
     fn write_pixel(&mut self, content: &Self::Content, x: usize, y: usize);
     fn bounds(&self) -> Rect;
@@ -18,13 +17,24 @@ pub trait Drawable {
         self.bounds().size()
     }
 
+    /// Whether shapes are drawn with their edges partially covering pixels.
+    fn anti_aliasing(&self) -> bool {
+        false
+    }
+
+    /// Writes a pixel that `content` only covers `coverage` (out of 255) of.
+    /// By default any covered pixel is written whole.
+    fn write_pixel_coverage(&mut self, content: &Self::Content, x: usize, y: usize, coverage: u8) {
+        if coverage > 0 {
+            self.write_pixel(content, x, y);
+        }
+    }
+
     fn execute_draw_commands(&mut self, commands: &[DrawCommand<Self::Content>]) {
         for command in commands {
             match command {
-                DrawCommand::TextLine(_, _, _) => {
-                    unimplemented!(
-                        "At the moment, text is being rendered as glyphs through BitMap."
-                    );
+                DrawCommand::TextLine(bounds, text, font, content, palette) => {
+                    self.draw_text_line(bounds, text, font, content, palette);
                 }
                 DrawCommand::FillRect(bounds, content) => {
                     self.fill_rect(bounds, content);
@@ -91,31 +101,91 @@ pub trait Drawable {
                     }
                 }
                 DrawCommand::Bitmap(bitmap, bounds) => {
-                    assert_eq!(
-                        bitmap.len(),
-                        bounds.width * bounds.height,
-                        "Bitmap command dimensions don't match the map"
-                    );
-
-                    for dy in 0..(bounds.height) {
-                        for dx in 0..(bounds.width) {
-                            let x = dx + bounds.x as usize;
-                            let y = dy + bounds.y as usize;
-
-                            let Some(Some(content)) = bitmap.get(dy * bounds.width + dx) else {
-                                continue;
-                            };
-
-                            self.write_pixel(content, x, y);
-                        }
-                    }
+                    self.draw_bitmap(bitmap, bounds);
                 }
                 DrawCommand::StrokeShape(bounds, stroke_width, shape, content) => {
-                    self.stroke_shape(bounds, stroke_width.clone(), shape, content.clone());
+                    self.stroke_shape(bounds, *stroke_width, shape, content.clone());
                 }
                 DrawCommand::FillShape(bounds, shape, content) => {
                     self.fill_shape(bounds, shape, content.clone());
                 }
+                DrawCommand::FillGradient(bounds, shape, gradient) => {
+                    self.fill_gradient(bounds, shape, gradient);
+                }
+                DrawCommand::Effect(bounds, effect, commands) => {
+                    self.draw_effect(bounds, effect, commands);
+                }
+            }
+        }
+    }
+
+    /// Draws `commands` offscreen, in `bounds` grown by how far the effect spreads,
+    /// and composites them back processed by `effect`.
+    fn draw_effect(&mut self, bounds: &Rect, effect: &Effect<Self::Content>, commands: &[DrawCommand<Self::Content>]) {
+        let spread = effect.spread();
+        let region = Rect::new(
+            bounds.x - spread as i64,
+            bounds.y - spread as i64,
+            bounds.width + 2 * spread,
+            bounds.height + 2 * spread
+        );
+
+        let mut layer = Offscreen::new(&region).with_anti_aliasing(self.anti_aliasing());
+        layer.execute_draw_commands(commands);
+
+        match effect {
+            Effect::Blur(radius) => layer.blurred(*radius).composite(self, 0, 0),
+            Effect::Shadow { radius, offset, content } => {
+                layer.silhouette(content).blurred(*radius).composite(self, offset.x(), offset.y());
+                self.execute_draw_commands(commands);
+            }
+        }
+    }
+
+    fn draw_bitmap(&mut self, bitmap: &[Option<Self::Content>], bounds: &Rect) {
+        assert_eq!(
+            bitmap.len(),
+            bounds.width * bounds.height,
+            "Bitmap command dimensions don't match the map"
+        );
+
+        for dy in 0..(bounds.height) {
+            for dx in 0..(bounds.width) {
+                let x = dx + bounds.x as usize;
+                let y = dy + bounds.y as usize;
+
+                let Some(Some(content)) = bitmap.get(dy * bounds.width + dx) else {
+                    continue;
+                };
+
+                self.write_pixel(content, x, y);
+            }
+        }
+    }
+
+    /// Draws `text` as a single line with the glyphs of `font`. Backends that can draw text
+    /// on their own can override this to get the whole string instead of one bitmap per glyph.
+    fn draw_text_line(&mut self, bounds: &Rect, text: &str, font: &Font, content: &Self::Content, palette: &[Self::Content]) {
+        let resolved_text = font.calculate_lines(text, &Rect::sized(bounds.width, bounds.height));
+
+        for line in resolved_text.lines() {
+            for resolved_glyph in &line.glyphs {
+                let size = resolved_glyph.size();
+                let offset = resolved_glyph.offset();
+
+                // Glyphs with the wrong number of points are cut or filled with empty points,
+                // so a broken glyph table can't make the bitmap fail to draw.
+                let mut bitmap = resolved_glyph.glyph().paint(content, palette);
+                bitmap.resize(size.width * size.height, None);
+
+                let glyph_bounds = Rect::new(
+                    bounds.x + line.bounds().x + offset.width as i64,
+                    bounds.y + line.bounds().y + offset.height as i64,
+                    size.width,
+                    size.height
+                );
+
+                self.draw_bitmap(&bitmap, &glyph_bounds);
             }
         }
     }
@@ -157,9 +227,34 @@ pub trait Drawable {
             Shape::Capsule => {
                 self.stroke_path(bounds, stroke_width, self.capsule_path(bounds), &content)
             }
+            Shape::Path(path) => {
+                if let Some(outline) = path.outline(bounds) {
+                    self.stroke_path(bounds, stroke_width, outline, &content);
+                }
+            }
+            Shape::Polygon(_) | Shape::Star(..) | Shape::Arc(..) | Shape::Pie(..) | Shape::Line(..) => {
+                if let Some(outline) = self.inset_outline(bounds, stroke_width, shape) {
+                    self.stroke_path(bounds, stroke_width, outline, &content);
+                }
+            }
         }
     }
 
+    /// The outline of a shape drawn from a path, placed half of `stroke_width` in from the edges.
+    /// The stroke goes on both sides of the outline, so this keeps all of it within the bounds.
+    fn inset_outline(&self, bounds: &Rect, stroke_width: usize, shape: &Shape) -> Option<tiny_skia::Path> {
+        let inset = stroke_width as f32 / 2.0;
+        let inner_bounds = Rect::sized(
+            bounds.width.saturating_sub(stroke_width),
+            bounds.height.saturating_sub(stroke_width),
+        );
+
+        shape
+            .path()
+            .and_then(|path| path.outline(&inner_bounds))
+            .and_then(|outline| outline.transform(tiny_skia::Transform::from_translate(inset, inset)))
+    }
+
     fn stroke_path(
         &mut self,
         bounds: &Rect,
@@ -167,48 +262,36 @@ pub trait Drawable {
         path: tiny_skia::Path,
         content: &Self::Content,
     ) {
+        let pixmap = self.stroke_mask(bounds, stroke_width, &path);
+
+        self.write_coverage(&pixmap, bounds, content);
+    }
+
+    /// A pixmap as big as `bounds` where `path` was stroked.
+    fn stroke_mask(&self, bounds: &Rect, stroke_width: usize, path: &tiny_skia::Path) -> tiny_skia::Pixmap {
         // FIXME: stroke width is a little broken, because skia draws within the bounds provided,
         // but because the stroke goes on either side of the path, half of it ends up outside of the bounds.
         // maybe scaling the bounds by the corner radius amount, and then scaling the drawing back.
         let mut paint = tiny_skia::Paint::default();
         paint.set_color_rgba8(u8::MAX, u8::MAX, u8::MAX, u8::MAX);
-        paint.anti_alias = false;
+        paint.anti_alias = self.anti_aliasing();
 
-        let mut stroke = tiny_skia::Stroke::default();
-        stroke.width = stroke_width as f32;
-        stroke.line_cap = tiny_skia::LineCap::Round;
+        let stroke = tiny_skia::Stroke {
+            width: stroke_width as f32,
+            line_cap: tiny_skia::LineCap::Round,
+            ..Default::default()
+        };
 
         let mut pixmap = tiny_skia::Pixmap::new(bounds.width as u32, bounds.height as u32).unwrap();
         pixmap.stroke_path(
-            &path,
+            path,
             &paint,
             &stroke,
             tiny_skia::Transform::identity(),
             None,
         );
 
-        let pixels = pixmap.pixels();
-
-        let x = bounds.x as usize;
-        let y = bounds.y as usize;
-
-        for i in 0..pixels.len() {
-            let pixel = pixels[i];
-            let color = pixel.demultiply();
-
-            if color.red() == 0 && color.green() == 0 && color.blue() == 0 && color.alpha() == 0 {
-                // Not filled in. Skip
-                continue;
-            }
-
-            let dx = i % bounds.width as usize;
-            let dy = i / bounds.width as usize;
-
-            let x = x + dx;
-            let y = y + dy;
-
-            self.write_pixel(content, x, y);
-        }
+        pixmap
     }
 
     fn fill_shape(&mut self, bounds: &Rect, shape: &Shape, content: Self::Content) {
@@ -218,7 +301,7 @@ pub trait Drawable {
 
         match shape {
             Shape::Rectangle => {
-                self.fill_rect(&bounds, &content);
+                self.fill_rect(bounds, &content);
             }
             Shape::RoundedRectangle(corner_radius) => {
                 self.fill_path(
@@ -231,42 +314,115 @@ pub trait Drawable {
                 self.fill_path(bounds, self.ellipse_path(bounds), &content);
             }
             Shape::Capsule => self.fill_path(bounds, self.capsule_path(bounds), &content),
+            Shape::Path(_) | Shape::Polygon(_) | Shape::Star(..) | Shape::Arc(..) | Shape::Pie(..) => {
+                if let Some(outline) = shape.path().and_then(|path| path.outline(bounds)) {
+                    self.fill_path(bounds, outline, &content);
+                }
+            }
+            Shape::Line(..) => self.stroke_shape(bounds, 1, shape, content),
         }
     }
 
     fn fill_path(&mut self, bounds: &Rect, path: tiny_skia::Path, content: &Self::Content) {
+        let pixmap = self.fill_mask(bounds, &path);
+
+        self.write_coverage(&pixmap, bounds, content);
+    }
+
+    /// Fills `shape` with the content of `gradient` at each of its pixels.
+    fn fill_gradient(&mut self, bounds: &Rect, shape: &Shape, gradient: &Gradient<Self::Content>) {
+        if bounds.width == 0 || bounds.height == 0 {
+            return;
+        }
+
+        let mask = match shape {
+            Shape::Rectangle => None,
+            Shape::RoundedRectangle(corner_radius) => Some(self.fill_mask(bounds, &self.rounded_rectangle_path(bounds, corner_radius))),
+            Shape::Ellipse => Some(self.fill_mask(bounds, &self.ellipse_path(bounds))),
+            Shape::Capsule => Some(self.fill_mask(bounds, &self.capsule_path(bounds))),
+            Shape::Path(_) | Shape::Polygon(_) | Shape::Star(..) | Shape::Arc(..) | Shape::Pie(..) => {
+                let Some(outline) = shape.path().and_then(|path| path.outline(bounds)) else { return };
+
+                Some(self.fill_mask(bounds, &outline))
+            }
+            // There's nothing to fill in a line, so it's stroked one pixel thick, like `fill_shape` does.
+            Shape::Line(..) => {
+                let Some(outline) = self.inset_outline(bounds, 1, shape) else { return };
+
+                Some(self.stroke_mask(bounds, 1, &outline))
+            }
+        };
+
+        let size = bounds.size();
+        let drawable = self.bounds();
+
+        // Only the part of the bounds that's on the drawable.
+        let columns = (drawable.x - bounds.x).max(0)..(drawable.max_x() - bounds.x).clamp(0, bounds.width as i64);
+        let rows = (drawable.y - bounds.y).max(0)..(drawable.max_y() - bounds.y).clamp(0, bounds.height as i64);
+
+        for dy in rows.start as usize..rows.end as usize {
+            for dx in columns.start as usize..columns.end as usize {
+                let coverage = match &mask {
+                    Some(mask) => self.mask_coverage(mask.pixels()[dy * bounds.width + dx]),
+                    None => u8::MAX,
+                };
+
+                if coverage == 0 {
+                    continue;
+                }
+
+                let Some(content) = gradient.content_at(dx, dy, &size) else { return };
+
+                let (x, y) = (bounds.x + dx as i64, bounds.y + dy as i64);
+                self.write_pixel_coverage(&content, x as usize, y as usize, coverage);
+            }
+        }
+    }
+
+    /// A pixmap as big as `bounds` where `path` was filled in.
+    fn fill_mask(&self, bounds: &Rect, path: &tiny_skia::Path) -> tiny_skia::Pixmap {
         let mut paint = tiny_skia::Paint::default();
         paint.set_color_rgba8(u8::MAX, u8::MAX, u8::MAX, u8::MAX);
         let mut pixmap = tiny_skia::Pixmap::new(bounds.width as u32, bounds.height as u32).unwrap();
         pixmap.fill_path(
-            &path,
+            path,
             &paint,
             tiny_skia::FillRule::Winding,
             tiny_skia::Transform::identity(),
             None,
         );
 
-        let pixels = pixmap.pixels();
+        pixmap
+    }
+
+    /// How much of a pixel a path drawn on a pixmap covers.
+    fn mask_coverage(&self, pixel: tiny_skia::PremultipliedColorU8) -> u8 {
+        match pixel.alpha() {
+            // Not filled in.
+            0 => 0,
+            // Without anti-aliasing, the faint edges skia draws for fills are written whole.
+            alpha if self.anti_aliasing() => alpha,
+            _ => u8::MAX,
+        }
+    }
 
+    /// Writes `content` where the path was drawn on `pixmap`, which is as big as `bounds`.
+    /// The alpha of the pixmap is how much of each pixel the path covers.
+    fn write_coverage(&mut self, pixmap: &tiny_skia::Pixmap, bounds: &Rect, content: &Self::Content) {
         let x = bounds.x as usize;
         let y = bounds.y as usize;
 
-        for i in 0..pixels.len() {
-            let pixel = pixels[i];
-            let color = pixel.demultiply();
+        for (i, pixel) in pixmap.pixels().iter().enumerate() {
+            let coverage = self.mask_coverage(*pixel);
 
-            if color.red() == 0 && color.green() == 0 && color.blue() == 0 && color.alpha() == 0 {
-                // Not filled in. Skip
+            if coverage == 0 {
                 continue;
             }
 
-            let dx = i % bounds.width as usize;
-            let dy = i / bounds.width as usize;
-
-            let x = x + dx;
-            let y = y + dy;
+            let dx = i % bounds.width;
+            let dy = i / bounds.width;
 
-            self.write_pixel(content, x, y);
+            self.write_pixel_coverage(content, x + dx, y + dy, coverage);
         }
     }
 
@@ -295,7 +451,7 @@ pub trait Drawable {
         let initial_x = skia_bounds.x() + corners.top_left as f32;
         let initial_y = 0.0 + jankiness_correction;
 
-        path_builder.move_to(initial_x as f32, initial_y as f32);
+        path_builder.move_to(initial_x, initial_y);
 
         let top_right_start_x = skia_bounds.width() - corners.top_right as f32;
 
@@ -387,18 +543,132 @@ pub trait Drawable {
     }
 }
 
-impl<Content: Clone + Default> Drawable for Canvas<Content> {
+impl<Content: Clone + Default + Blend> Drawable for Canvas<Content> {
     type Content = Content;
 
     fn fill_rect(&mut self, bounds: &Rect, content: &Self::Content) {
-        self.draw_rect(bounds, content);
+        for y in bounds.y.max(0)..bounds.max_y().min(self.size.height as i64) {
+            for x in bounds.x.max(0)..bounds.max_x().min(self.size.width as i64) {
+                self.write_pixel(content, x as usize, y as usize);
+            }
+        }
     }
 
+    /// Draws `content` over the pixel that's already there.
     fn write_pixel(&mut self, content: &Self::Content, x: usize, y: usize) {
-        self.write(content, x, y);
+        let Some(below) = self.get_at(x, y) else { return };
+
+        let blended = content.over(below);
+        self.write(&blended, x, y);
+    }
+
+    fn write_pixel_coverage(&mut self, content: &Self::Content, x: usize, y: usize, coverage: u8) {
+        let Some(below) = self.get_at(x, y) else { return };
+
+        let blended = content.partially_over(below, coverage);
+        self.write(&blended, x, y);
+    }
+
+    fn anti_aliasing(&self) -> bool {
+        self.anti_aliasing
     }
 
     fn bounds(&self) -> Rect {
         Rect::from_size(&self.size)
     }
 }
+
+#[test]
+fn blends_anti_aliased_edges() {
+    use crate::graphics::pixel::RGBPixel;
+
+    let (white, black) = (RGBPixel::new(255, 255, 255), RGBPixel::new(0, 0, 0));
+
+    let draw = |anti_aliasing: bool| -> Vec<RGBPixel> {
+        let mut canvas: Canvas<RGBPixel> = Canvas::create(9, 9).with_anti_aliasing(anti_aliasing);
+        canvas.clear_with(&black);
+        canvas.fill_shape(&Rect::sized(9, 9), &Shape::Ellipse, white.clone());
+
+        canvas.contents
+    };
+
+    let is_edge = |pixel: &RGBPixel| *pixel != white && *pixel != black;
+
+    assert!(!draw(false).iter().any(is_edge));
+    assert!(draw(true).iter().any(is_edge));
+
+    // The middle of the shape is fully covered either way.
+    assert_eq!(draw(true)[4 * 9 + 4], white);
+}
+
+#[test]
+fn fills_shapes_from_paths() {
+    use crate::graphics::pixel::RGBPixel;
+
+    let (white, black) = (RGBPixel::new(255, 255, 255), RGBPixel::new(0, 0, 0));
+
+    let draw = |shape: Shape| -> Vec<bool> {
+        let mut canvas: Canvas<RGBPixel> = Canvas::create(10, 10);
+        canvas.clear_with(&black);
+        canvas.fill_shape(&Rect::sized(10, 10), &shape, white.clone());
+
+        canvas.contents.iter().map(|pixel| *pixel == white).collect()
+    };
+
+    // A quarter, from the right of the center to the bottom.
+    let pie = draw(Shape::Pie(0.0, 90.0));
+    assert_eq!([pie[7 * 10 + 7], pie[2 * 10 + 2], pie[2 * 10 + 7], pie[7 * 10 + 2]], [true, false, false, false]);
+
+    // A diamond leaves the corners out.
+    let square = draw(Shape::Polygon(4));
+    assert_eq!([square[5 * 10 + 5], square[0], square[9 * 10 + 9]], [true, false, false]);
+
+    assert!(!draw(Shape::Polygon(2)).contains(&true));
+    assert!(draw(Shape::Star(5, 0.5))[5 * 10 + 5]);
+
+    // Lines are drawn one pixel thick when filled, from corner to corner here.
+    let line = draw(Shape::Line((0.0, 0.0), (1.0, 1.0)));
+    assert_eq!([line[0], line[5 * 10 + 5], line[9 * 10 + 9], line[9]], [true, true, true, false]);
+}
+
+#[test]
+fn draws_pixels_that_dont_blend() {
+    #[derive(Clone, Default, Debug, PartialEq)]
+    struct Shade(u8);
+
+    impl Blend for Shade {}
+
+    let mut canvas: Canvas<Shade> = Canvas::create(2, 1);
+    canvas.fill_rect(&Rect::sized(2, 1), &Shade(1));
+    canvas.fill_rect(&Rect::sized(1, 1), &Shade(2));
+
+    assert_eq!(canvas.contents, vec![Shade(2), Shade(1)]);
+}
+
+#[test]
+fn clips_gradients_to_the_canvas() {
+    use crate::graphics::pixel::RGBPixel;
+
+    let (white, black) = (RGBPixel::new(255, 255, 255), RGBPixel::new(0, 0, 0));
+    let gradient = Gradient::horizontal().stop(0.0, white.clone()).stop(1.0, white.clone());
+
+    // Half of it is above and to the left of the canvas.
+    let mut canvas: Canvas<RGBPixel> = Canvas::create(4, 4);
+    canvas.clear_with(&black);
+    canvas.fill_gradient(&Rect::new(-2, -2, 4, 4), &Shape::Rectangle, &gradient);
+
+    let filled: Vec<_> = canvas.contents.iter().map(|pixel| *pixel == white).collect();
+    assert_eq!(filled, vec![
+        true, true, false, false,
+        true, true, false, false,
+        false, false, false, false,
+        false, false, false, false,
+    ]);
+
+    // Lines are stroked one pixel thick, like when they're filled with a single content.
+    let mut canvas: Canvas<RGBPixel> = Canvas::create(4, 4);
+    canvas.clear_with(&black);
+    canvas.fill_gradient(&Rect::sized(4, 4), &Shape::Line((0.0, 0.0), (1.0, 1.0)), &gradient);
+
+    assert_eq!([&canvas.contents[0], &canvas.contents[15], &canvas.contents[3]], [&white, &white, &black]);
+}
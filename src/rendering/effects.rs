@@ -0,0 +1,72 @@
+use crate::layout::geometry::Vector;
+
+/// Processing applied to everything drawn by a subtree, drawn offscreen first.
+#[derive(Clone, Debug)]
+pub enum Effect<Content> {
+    /// Blurs the drawing, spreading each pixel `radius` pixels away.
+    Blur(usize),
+    /// Draws a silhouette of the drawing behind it, filled with `content`, blurred by `radius` and moved by `offset`.
+    Shadow { radius: usize, offset: Vector, content: Content }
+}
+
+impl<Content> Effect<Content> {
+    /// How far past the bounds of the drawing the effect spreads it, before it's moved by any offset.
+    pub fn spread(&self) -> usize {
+        match self {
+            Effect::Blur(radius) | Effect::Shadow { radius, .. } => *radius
+        }
+    }
+}
+
+#[test]
+fn draws_effects_around_nodes() {
+    use crate::{
+        graphics::{canvas::Canvas, pixel::RGBPixel},
+        layout::{
+            geometry::Shape,
+            node::{Node, ShapeBehavior},
+            size_resolution::{SizeCalculator, SizeResolver},
+        },
+        rendering::canvas::Drawable,
+    };
+
+    let (white, black) = (RGBPixel::new(255, 255, 255), RGBPixel::new(0, 0, 0));
+
+    // A 2x2 square at 2, 2, and which pixels of each row are drawn on.
+    let draw = |effect: fn(Node<RGBPixel, ()>) -> Node<RGBPixel, ()>| -> Vec<String> {
+        let square = Node::Shape(Shape::Rectangle, ShapeBehavior::Fill, white.clone()).width(2).height(2);
+        let layout = effect(square).padding_all(2);
+
+        let mut canvas: Canvas<RGBPixel> = Canvas::create(10, 6);
+        canvas.clear_with(&black);
+
+        let sized = SizeCalculator::resolve_size(&layout, &canvas.bounds(), &mut ());
+        canvas.execute_draw_commands(&SizeResolver::resolve_draw_commands(&sized, &sized.sizing.fit_into(&canvas.bounds())));
+
+        canvas
+            .contents
+            .chunks(10)
+            .map(|row| row.iter().map(|pixel| if *pixel == white { '#' } else if *pixel == black { '.' } else { '+' }).collect())
+            .collect()
+    };
+
+    // Blurred 1 pixel around the square.
+    assert_eq!(draw(|node| node.blur(1)), vec![
+        "..........",
+        ".++++.....",
+        ".++++.....",
+        ".++++.....",
+        ".++++.....",
+        "..........",
+    ]);
+
+    // The shadow is as big as the blurred square, moved 3 pixels right and 1 down, under the square.
+    assert_eq!(draw(|node| node.shadow(1, Vector::new(3, 1), RGBPixel::new(0, 0, 255))), vec![
+        "..........",
+        "..........",
+        "..##++++..",
+        "..##++++..",
+        "....++++..",
+        "....++++..",
+    ]);
+}
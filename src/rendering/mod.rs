@@ -1,15 +1,26 @@
 pub mod canvas;
+pub mod effects;
+pub mod offscreen;
 
-use crate::layout::geometry::{Rect, Shape};
+use std::sync::Arc;
+
+use crate::{fonts::Font, graphics::gradient::Gradient, layout::geometry::{Rect, Shape}};
+
+use effects::Effect;
 
 // FIXME: Should the draw commands contain the content (pixel value or color)?
 // or can they be an environment value, to save on some memory?
 // A draw command that sets and resets current content would be nice.
 pub enum DrawCommand<Content: Clone + Default> {
-    TextLine(Rect, String, Content),
+    /// A run of text on one line, drawn with a single font and content, to be laid out again in its bounds.
+    /// Glyphs with palette points take their colors from the last field.
+    TextLine(Rect, String, Arc<Font>, Content, Vec<Content>),
     FillRect(Rect, Content),
     StrokeRect(Rect, usize, Content),
     Bitmap(Vec<Option<Content>>, Rect),
     StrokeShape(Rect, usize, Shape, Content),
-    FillShape(Rect, Shape, Content)
+    FillShape(Rect, Shape, Content),
+    FillGradient(Rect, Shape, Gradient<Content>),
+    /// Commands drawn offscreen and processed by the effect, in the region of the rect.
+    Effect(Rect, Effect<Content>, Vec<DrawCommand<Content>>)
 }
\ No newline at end of file
@@ -0,0 +1,203 @@
+use crate::{graphics::pixel::Blend, layout::geometry::Rect};
+
+use super::canvas::Drawable;
+
+/// A drawable that keeps what's drawn in a region of another drawable's space, to process it
+/// before it's composited back. Pixels keep how much of them was drawn on, and are empty until then.
+#[derive(Clone, Debug)]
+pub struct Offscreen<Content: Clone + Default + Blend> {
+    region: Rect,
+    pixels: Vec<Option<Content>>,
+    coverage: Vec<u8>,
+    anti_aliasing: bool
+}
+
+impl<Content: Clone + Default + Blend> Offscreen<Content> {
+    pub fn new(region: &Rect) -> Self {
+        let count = region.width * region.height;
+
+        Offscreen {
+            region: region.clone(),
+            pixels: vec![None; count],
+            coverage: vec![0; count],
+            anti_aliasing: false
+        }
+    }
+
+    pub fn with_anti_aliasing(mut self, anti_aliasing: bool) -> Self {
+        self.anti_aliasing = anti_aliasing;
+
+        self
+    }
+
+    pub fn region(&self) -> &Rect {
+        &self.region
+    }
+
+    /// The content and coverage of the pixel at `x`, `y` of the region.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<(&Content, u8)> {
+        if x >= self.region.width || y >= self.region.height {
+            return None;
+        }
+
+        let index = y * self.region.width + x;
+
+        self.pixels[index].as_ref().map(|content| (content, self.coverage[index]))
+    }
+
+    /// Every drawn pixel replaced by `content`, keeping its coverage.
+    pub fn silhouette(&self, content: &Content) -> Self {
+        Offscreen {
+            pixels: self.pixels.iter().map(|pixel| pixel.as_ref().map(|_| content.clone())).collect(),
+            ..self.clone()
+        }
+    }
+
+    /// A box blur, spreading each pixel `radius` pixels away in every direction.
+    /// Contents are mixed weighted by how much of them was drawn.
+    pub fn blurred(&self, radius: usize) -> Self {
+        let (width, height) = (self.region.width, self.region.height);
+
+        let horizontal = self.blur_pass(radius, |x, y| y * width + x, width, height);
+
+        horizontal.blur_pass(radius, |y, x| y * width + x, height, width)
+    }
+
+    /// Blurs along lines of `length` pixels, `count` of them. `index` takes the position
+    /// along a line and the line, and returns where the pixel is.
+    fn blur_pass<Index: Fn(usize, usize) -> usize>(&self, radius: usize, index: Index, length: usize, count: usize) -> Self {
+        let mut blurred = Offscreen::new(&self.region).with_anti_aliasing(self.anti_aliasing);
+        let window = 2 * radius as u32 + 1;
+
+        for line in 0..count {
+            for position in 0..length {
+                let samples = position.saturating_sub(radius)..(position + radius + 1).min(length);
+
+                let mut mixed: Option<Content> = None;
+                let mut total_coverage = 0;
+
+                for sample in samples {
+                    let sample_index = index(sample, line);
+                    let Some(content) = &self.pixels[sample_index] else { continue };
+
+                    let coverage = self.coverage[sample_index] as u32;
+                    if coverage == 0 {
+                        continue;
+                    }
+
+                    // A running mean: each sample is mixed in by its share of the coverage so far.
+                    let share = (coverage * 255 / (total_coverage + coverage)) as u8;
+                    mixed = Some(match mixed {
+                        Some(mixed) => content.partially_over(&mixed, share),
+                        None => content.clone()
+                    });
+
+                    total_coverage += coverage;
+                }
+
+                let target = index(position, line);
+                blurred.pixels[target] = mixed;
+                blurred.coverage[target] = (total_coverage / window) as u8;
+            }
+        }
+
+        blurred
+    }
+
+    /// Draws the pixels onto `target`, moved by `dx`, `dy`.
+    pub fn composite<D: Drawable<Content = Content> + ?Sized>(&self, target: &mut D, dx: i64, dy: i64) {
+        for y in 0..self.region.height {
+            for x in 0..self.region.width {
+                let Some((content, coverage)) = self.pixel(x, y) else { continue };
+
+                let target_x = self.region.x + x as i64 + dx;
+                let target_y = self.region.y + y as i64 + dy;
+
+                if coverage == 0 || target_x < 0 || target_y < 0 {
+                    continue;
+                }
+
+                target.write_pixel_coverage(content, target_x as usize, target_y as usize, coverage);
+            }
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        let x = x as i64 - self.region.x;
+        let y = y as i64 - self.region.y;
+
+        if x < 0 || y < 0 || x >= self.region.width as i64 || y >= self.region.height as i64 {
+            return None;
+        }
+
+        Some(y as usize * self.region.width + x as usize)
+    }
+}
+
+impl<Content: Clone + Default + Blend> Drawable for Offscreen<Content> {
+    type Content = Content;
+
+    fn fill_rect(&mut self, bounds: &Rect, content: &Self::Content) {
+        for y in bounds.y.max(0)..bounds.max_y() {
+            for x in bounds.x.max(0)..bounds.max_x() {
+                self.write_pixel(content, x as usize, y as usize);
+            }
+        }
+    }
+
+    fn write_pixel(&mut self, content: &Self::Content, x: usize, y: usize) {
+        self.write_pixel_coverage(content, x, y, u8::MAX);
+    }
+
+    fn write_pixel_coverage(&mut self, content: &Self::Content, x: usize, y: usize, coverage: u8) {
+        let Some(index) = self.index(x, y) else { return };
+
+        if coverage == 0 {
+            return;
+        }
+
+        self.pixels[index] = Some(match &self.pixels[index] {
+            Some(below) => content.partially_over(below, coverage),
+            None => content.clone()
+        });
+
+        // What's left uncovered gets covered by the same share.
+        let uncovered = (u8::MAX - self.coverage[index]) as u32;
+        self.coverage[index] += ((uncovered * coverage as u32 + 127) / 255) as u8;
+    }
+
+    /// Covers everything up to the far edges of the region, for the drawing code that
+    /// clips to the bounds from the origin. Pixels outside the region are left out.
+    fn bounds(&self) -> Rect {
+        Rect::sized(
+            (self.region.max_x().max(0)) as usize,
+            (self.region.max_y().max(0)) as usize
+        )
+    }
+
+    fn anti_aliasing(&self) -> bool {
+        self.anti_aliasing
+    }
+}
+
+#[test]
+fn blurs_drawn_pixels() {
+    use crate::graphics::pixel::RGBPixel;
+
+    let white = RGBPixel::new(255, 255, 255);
+
+    let mut offscreen: Offscreen<RGBPixel> = Offscreen::new(&Rect::new(10, 10, 5, 1));
+    offscreen.fill_rect(&Rect::new(12, 10, 1, 1), &white);
+    // Outside of the region.
+    offscreen.fill_rect(&Rect::new(0, 0, 5, 5), &white);
+
+    assert_eq!(offscreen.pixel(2, 0), Some((&white, u8::MAX)));
+    assert_eq!(offscreen.pixel(0, 0), None);
+
+    let blurred = offscreen.blurred(1);
+    let coverage: Vec<_> = (0..5).map(|x| blurred.pixel(x, 0).map(|(_, coverage)| coverage)).collect();
+
+    // Spread over the 3 pixels of the window horizontally, and 3 vertically (only one row is drawn).
+    assert_eq!(coverage, vec![None, Some(28), Some(28), Some(28), None]);
+    assert_eq!(blurred.pixel(1, 0).map(|(content, _)| content), Some(&white));
+}